@@ -1,13 +1,56 @@
 //! Simple COBOL parser using Rowan
 //!
 //! Supports:
-//! - IDENTIFICATION DIVISION.
-//! - PROGRAM-ID. <name>.
-//! - PROCEDURE DIVISION.
-//! - DISPLAY "<string>".
+//! - IDENTIFICATION (or ID) DIVISION with PROGRAM-ID, including quoted
+//!   names and INITIAL/COMMON/RECURSIVE, and the AUTHOR, INSTALLATION,
+//!   DATE-WRITTEN, DATE-COMPILED, SECURITY and REMARKS comment entries.
+//! - ENVIRONMENT DIVISION: SOURCE-COMPUTER, OBJECT-COMPUTER, SPECIAL-NAMES
+//!   and FILE-CONTROL entries.
+//! - DATA DIVISION with FILE, WORKING-STORAGE, LOCAL-STORAGE and LINKAGE
+//!   sections and nested data description entries.
+//! - PICTURE, USAGE, VALUE, OCCURS, REDEFINES, RENAMES, JUSTIFIED, SIGN,
+//!   BLANK WHEN ZERO and SYNCHRONIZED clauses; PICTURE strings are analysed
+//!   by the [`picture`] module and records laid out by the [`layout`] module.
+//! - PROCEDURE DIVISION organised into sections, paragraphs and sentences.
+//! - MOVE [CORRESPONDING], with subscripted and reference-modified items.
+//! - ADD, SUBTRACT, MULTIPLY, DIVIDE and COMPUTE with arithmetic expressions.
+//! - IF/ELSE/END-IF with relation, class, sign and condition-name
+//!   conditions, including abbreviated combined relations.
+//! - EVALUATE with ALSO, ANY, TRUE/FALSE, THRU and WHEN OTHER.
+//! - PERFORM, out-of-line or inline, with TIMES, UNTIL and VARYING loops.
+//! - GO TO [DEPENDING ON], EXIT, STOP RUN, GOBACK, CONTINUE, NEXT SENTENCE
+//!   and ALTER, which is reported in [`Parse::warnings`].
+//! - STRING, UNSTRING and INSPECT (TALLYING, REPLACING, CONVERTING).
+//! - OPEN, CLOSE, READ, WRITE, REWRITE, DELETE and START, linked to the
+//!   SELECT entry and FD of their file.
+//! - CALL and CANCEL with static or dynamic targets and BY REFERENCE,
+//!   CONTENT or VALUE arguments, and PROCEDURE DIVISION USING/RETURNING.
+//! - DISPLAY of several operands with UPON and WITH NO ADVANCING, and
+//!   ACCEPT from the input device, DATE, DAY, TIME or the environment.
+//! - SEARCH, SEARCH ALL and SET, resolved to the table and its INDEXED BY
+//!   indexes.
+//! - SORT, MERGE, RELEASE and RETURN with their input and output
+//!   procedures, and SD entries in the FILE SECTION.
+//! - Identifiers qualified with OF/IN, subscripted with ALL, relative
+//!   indexes or expressions, and reference-modified by expressions.
+//! - Intrinsic FUNCTION calls, checked against the catalogue in
+//!   [`intrinsics`].
+//! - Alphanumeric, hexadecimal, national, numeric and figurative literals.
+//! - `*>` floating comments and fixed-form comment lines.
+//!
+//! Source text may be free-form or fixed-form (see [`SourceFormat`]).
+
+use std::collections::HashSet;
+use std::fmt;
 
 use rowan::{GreenNode, GreenNodeBuilder};
 
+pub mod intrinsics;
+pub mod layout;
+pub mod picture;
+
+use picture::{Picture, PictureError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(non_camel_case_types)]
 #[repr(u16)]
@@ -18,11 +61,270 @@ pub enum SyntaxKind {
     PROGRAM_ID_KW,
     PROCEDURE_KW,
     DISPLAY_KW,
+    DATA_KW,
+    SECTION_KW,
+    FILE_KW,
+    WORKING_STORAGE_KW,
+    LOCAL_STORAGE_KW,
+    LINKAGE_KW,
+    FD_KW,
+    FILLER_KW,
+    PICTURE_KW,
+    IS_KW,
+    USAGE_KW,
+    COMP_KW,
+    COMP_1_KW,
+    COMP_2_KW,
+    COMP_3_KW,
+    COMP_5_KW,
+    BINARY_KW,
+    PACKED_DECIMAL_KW,
+    POINTER_KW,
+    INDEX_KW,
+    VALUE_KW,
+    ARE_KW,
+    THRU_KW,
+    OCCURS_KW,
+    TO_KW,
+    TIMES_KW,
+    DEPENDING_KW,
+    ON_KW,
+    ASCENDING_KW,
+    DESCENDING_KW,
+    KEY_KW,
+    INDEXED_KW,
+    BY_KW,
+    REDEFINES_KW,
+    RENAMES_KW,
+    JUSTIFIED_KW,
+    RIGHT_KW,
+    LEFT_KW,
+    SIGN_KW,
+    LEADING_KW,
+    TRAILING_KW,
+    SEPARATE_KW,
+    CHARACTER_KW,
+    BLANK_KW,
+    WHEN_KW,
+    SYNCHRONIZED_KW,
+    ENVIRONMENT_KW,
+    CONFIGURATION_KW,
+    SOURCE_COMPUTER_KW,
+    OBJECT_COMPUTER_KW,
+    SPECIAL_NAMES_KW,
+    DECIMAL_POINT_KW,
+    COMMA_KW,
+    INPUT_OUTPUT_KW,
+    FILE_CONTROL_KW,
+    I_O_CONTROL_KW,
+    SELECT_KW,
+    OPTIONAL_KW,
+    ASSIGN_KW,
+    ORGANIZATION_KW,
+    SEQUENTIAL_KW,
+    RELATIVE_KW,
+    LINE_KW,
+    ACCESS_KW,
+    MODE_KW,
+    RANDOM_KW,
+    DYNAMIC_KW,
+    RECORD_KW,
+    ALTERNATE_KW,
+    WITH_KW,
+    DUPLICATES_KW,
+    STATUS_KW,
+    AUTHOR_KW,
+    INSTALLATION_KW,
+    DATE_WRITTEN_KW,
+    DATE_COMPILED_KW,
+    SECURITY_KW,
+    REMARKS_KW,
+    PROGRAM_KW,
+    INITIAL_KW,
+    COMMON_KW,
+    RECURSIVE_KW,
+    MOVE_KW,
+    CORRESPONDING_KW,
+    ADD_KW,
+    SUBTRACT_KW,
+    MULTIPLY_KW,
+    DIVIDE_KW,
+    COMPUTE_KW,
+    FROM_KW,
+    INTO_KW,
+    GIVING_KW,
+    REMAINDER_KW,
+    ROUNDED_KW,
+    SIZE_KW,
+    ERROR_KW,
+    NOT_KW,
+    EQUAL_KW,
+    END_ADD_KW,
+    END_SUBTRACT_KW,
+    END_MULTIPLY_KW,
+    END_DIVIDE_KW,
+    END_COMPUTE_KW,
+    IF_KW,
+    THEN_KW,
+    ELSE_KW,
+    END_IF_KW,
+    AND_KW,
+    OR_KW,
+    GREATER_KW,
+    LESS_KW,
+    THAN_KW,
+    NUMERIC_KW,
+    ALPHABETIC_KW,
+    ALPHABETIC_LOWER_KW,
+    ALPHABETIC_UPPER_KW,
+    POSITIVE_KW,
+    NEGATIVE_KW,
+    EVALUATE_KW,
+    ALSO_KW,
+    OTHER_KW,
+    ANY_KW,
+    TRUE_KW,
+    FALSE_KW,
+    END_EVALUATE_KW,
+    PERFORM_KW,
+    UNTIL_KW,
+    TEST_KW,
+    BEFORE_KW,
+    AFTER_KW,
+    VARYING_KW,
+    END_PERFORM_KW,
+    GO_KW,
+    EXIT_KW,
+    STOP_KW,
+    RUN_KW,
+    GOBACK_KW,
+    CONTINUE_KW,
+    NEXT_KW,
+    SENTENCE_KW,
+    ALTER_KW,
+    PROCEED_KW,
+    PARAGRAPH_KW,
+    CYCLE_KW,
+    STRING_KW,
+    DELIMITED_KW,
+    OVERFLOW_KW,
+    END_STRING_KW,
+    UNSTRING_KW,
+    DELIMITER_KW,
+    COUNT_KW,
+    IN_KW,
+    TALLYING_KW,
+    END_UNSTRING_KW,
+    INSPECT_KW,
+    FOR_KW,
+    CHARACTERS_KW,
+    FIRST_KW,
+    REPLACING_KW,
+    CONVERTING_KW,
+    OPEN_KW,
+    CLOSE_KW,
+    READ_KW,
+    WRITE_KW,
+    REWRITE_KW,
+    DELETE_KW,
+    START_KW,
+    INPUT_KW,
+    OUTPUT_KW,
+    I_O_KW,
+    EXTEND_KW,
+    END_KW,
+    AT_KW,
+    INVALID_KW,
+    PREVIOUS_KW,
+    ADVANCING_KW,
+    PAGE_KW,
+    END_OF_PAGE_KW,
+    REEL_KW,
+    UNIT_KW,
+    REMOVAL_KW,
+    NO_KW,
+    REWIND_KW,
+    REVERSED_KW,
+    LOCK_KW,
+    END_READ_KW,
+    END_WRITE_KW,
+    END_REWRITE_KW,
+    END_DELETE_KW,
+    END_START_KW,
+    CALL_KW,
+    CANCEL_KW,
+    USING_KW,
+    REFERENCE_KW,
+    CONTENT_KW,
+    RETURNING_KW,
+    EXCEPTION_KW,
+    OMITTED_KW,
+    END_CALL_KW,
+    ACCEPT_KW,
+    UPON_KW,
+    DATE_KW,
+    DAY_KW,
+    DAY_OF_WEEK_KW,
+    TIME_KW,
+    SEARCH_KW,
+    END_SEARCH_KW,
+    SET_KW,
+    UP_KW,
+    DOWN_KW,
+    ADDRESS_KW,
+    OF_KW,
+    SORT_KW,
+    MERGE_KW,
+    RELEASE_KW,
+    RETURN_KW,
+    END_RETURN_KW,
+    SD_KW,
+    COLLATING_KW,
+    SEQUENCE_KW,
+    ORDER_KW,
+    FUNCTION_KW,
+    ALL_KW,
+    ZERO_KW,
+    SPACE_KW,
+    HIGH_VALUE_KW,
+    LOW_VALUE_KW,
+    QUOTE_KW,
+    NULL_KW,
     DOT,
+    COMMA,
+    SEMICOLON,
+    COLON,
+    L_PAREN,
+    R_PAREN,
+    PLUS,
+    MINUS,
+    STAR,
+    SLASH,
+    /// `**`
+    POWER,
+    EQUALS,
+    GREATER,
+    LESS,
+    GREATER_EQUALS,
+    LESS_EQUALS,
     STRING_LITERAL,
+    LITERAL_CONTINUATION,
+    HEX_LITERAL,
+    NATIONAL_LITERAL,
+    NULL_TERMINATED_LITERAL,
+    INTEGER_LITERAL,
+    DECIMAL_LITERAL,
+    PICTURE_STRING,
+    /// Free text of an AUTHOR, INSTALLATION, DATE-WRITTEN, DATE-COMPILED,
+    /// SECURITY or REMARKS paragraph, one token per line.
+    COMMENT_ENTRY,
     IDENT,
     WHITESPACE,
     NEWLINE,
+    SEQUENCE_AREA,
+    INDICATOR,
+    IDENTIFICATION_AREA,
+    COMMENT,
     ERROR,
 
     // Nodes
@@ -30,11 +332,341 @@ pub enum SyntaxKind {
     IDENTIFICATION_DIVISION,
     PROGRAM_ID_CLAUSE,
     PROCEDURE_DIVISION,
+    SECTION,
+    PARAGRAPH,
+    SENTENCE,
     DISPLAY_STMT,
+    MOVE_STMT,
+    DATA_REF,
+    SUBSCRIPTS,
+    REF_MOD,
+    ADD_STMT,
+    SUBTRACT_STMT,
+    MULTIPLY_STMT,
+    DIVIDE_STMT,
+    COMPUTE_STMT,
+    /// A receiving item, `name [ROUNDED [MODE IS mode]]`
+    RECEIVER,
+    GIVING_PHRASE,
+    REMAINDER_PHRASE,
+    ON_SIZE_ERROR,
+    NOT_ON_SIZE_ERROR,
+    BIN_EXPR,
+    PREFIX_EXPR,
+    PAREN_EXPR,
+    IF_STMT,
+    THEN_BRANCH,
+    ELSE_BRANCH,
+    RELATION_CONDITION,
+    /// Relational operator, e.g. `IS NOT GREATER THAN OR EQUAL TO`
+    REL_OP,
+    CLASS_CONDITION,
+    SIGN_CONDITION,
+    CONDITION_NAME,
+    NOT_CONDITION,
+    /// Two conditions joined by AND or OR
+    COMBINED_CONDITION,
+    PAREN_CONDITION,
+    EVALUATE_STMT,
+    EVALUATE_SUBJECT,
+    /// Consecutive WHEN phrases sharing one statement block
+    WHEN_BRANCH,
+    WHEN_PHRASE,
+    WHEN_OBJECT,
+    WHEN_OTHER,
+    PERFORM_STMT,
+    /// `procedure-name [THRU procedure-name]`
+    PROCEDURE_RANGE,
+    TIMES_PHRASE,
+    /// `[WITH] TEST BEFORE` or `[WITH] TEST AFTER`
+    TEST_PHRASE,
+    UNTIL_PHRASE,
+    /// `VARYING` or `AFTER` `id FROM x BY y UNTIL condition`
+    VARYING_PHRASE,
+    GO_TO_STMT,
+    EXIT_STMT,
+    STOP_STMT,
+    GOBACK_STMT,
+    CONTINUE_STMT,
+    NEXT_SENTENCE_STMT,
+    ALTER_STMT,
+    STRING_STMT,
+    /// Sending items with their `DELIMITED BY` phrase
+    STRING_SOURCE,
+    INTO_PHRASE,
+    POINTER_PHRASE,
+    ON_OVERFLOW,
+    NOT_ON_OVERFLOW,
+    UNSTRING_STMT,
+    DELIMITED_PHRASE,
+    /// `[ALL] delimiter`
+    UNSTRING_DELIMITER,
+    /// `identifier [DELIMITER IN identifier] [COUNT IN identifier]`
+    UNSTRING_RECEIVER,
+    TALLYING_PHRASE,
+    INSPECT_STMT,
+    /// `identifier FOR ...` of INSPECT TALLYING
+    TALLYING_CLAUSE,
+    REPLACING_PHRASE,
+    CONVERTING_PHRASE,
+    /// CHARACTERS, ALL, LEADING or FIRST with the items it applies to
+    INSPECT_GROUP,
+    INSPECT_ITEM,
+    /// `BEFORE|AFTER [INITIAL] value`
+    INSPECT_BOUNDARY,
+    OPEN_STMT,
+    /// An open mode with the files opened in it
+    OPEN_GROUP,
+    CLOSE_STMT,
+    READ_STMT,
+    WRITE_STMT,
+    REWRITE_STMT,
+    DELETE_STMT,
+    START_STMT,
+    FROM_PHRASE,
+    /// `KEY [IS] name` of READ, `KEY relational-operator name` of START
+    KEY_PHRASE,
+    ADVANCING_PHRASE,
+    AT_END,
+    NOT_AT_END,
+    INVALID_KEY,
+    NOT_INVALID_KEY,
+    AT_END_OF_PAGE,
+    NOT_AT_END_OF_PAGE,
+    CALL_STMT,
+    CANCEL_STMT,
+    /// `USING` of CALL or of the PROCEDURE DIVISION header
+    USING_PHRASE,
+    /// One argument or parameter, with the BY phrase that precedes it
+    ARGUMENT,
+    RETURNING_PHRASE,
+    ON_EXCEPTION,
+    NOT_ON_EXCEPTION,
+    ACCEPT_STMT,
+    SEARCH_STMT,
+    /// `VARYING identifier` of SEARCH
+    SEARCH_VARYING,
+    /// `WHEN condition statements` of SEARCH
+    SEARCH_WHEN,
+    SET_STMT,
+    /// `ADDRESS OF identifier`
+    ADDRESS_OF,
+    SORT_STMT,
+    MERGE_STMT,
+    /// `ON {ASCENDING | DESCENDING} KEY name...`
+    SORT_KEY,
+    /// `USING file...` of SORT and MERGE
+    SORT_USING,
+    /// `GIVING file...` of SORT and MERGE
+    SORT_GIVING,
+    INPUT_PROCEDURE,
+    OUTPUT_PROCEDURE,
+    RELEASE_STMT,
+    RETURN_STMT,
+    /// `FUNCTION name [(arguments)] [(start:length)]`
+    FUNCTION_CALL,
+    FUNCTION_ARGS,
+    LITERAL,
+    DATA_DIVISION,
+    FILE_SECTION,
+    WORKING_STORAGE_SECTION,
+    LOCAL_STORAGE_SECTION,
+    LINKAGE_SECTION,
+    FILE_DESCRIPTION,
+    DATA_ITEM,
+    PICTURE_CLAUSE,
+    USAGE_CLAUSE,
+    VALUE_CLAUSE,
+    VALUE_RANGE,
+    OCCURS_CLAUSE,
+    OCCURS_DEPENDING,
+    OCCURS_KEY,
+    INDEXED_BY,
+    REDEFINES_CLAUSE,
+    RENAMES_CLAUSE,
+    JUSTIFIED_CLAUSE,
+    SIGN_CLAUSE,
+    BLANK_WHEN_ZERO_CLAUSE,
+    SYNCHRONIZED_CLAUSE,
+    ENVIRONMENT_DIVISION,
+    CONFIGURATION_SECTION,
+    SOURCE_COMPUTER_PARAGRAPH,
+    OBJECT_COMPUTER_PARAGRAPH,
+    SPECIAL_NAMES_PARAGRAPH,
+    DECIMAL_POINT_CLAUSE,
+    SPECIAL_NAME_CLAUSE,
+    INPUT_OUTPUT_SECTION,
+    FILE_CONTROL_PARAGRAPH,
+    I_O_CONTROL_PARAGRAPH,
+    SELECT_ENTRY,
+    ASSIGN_CLAUSE,
+    ORGANIZATION_CLAUSE,
+    ACCESS_MODE_CLAUSE,
+    RECORD_KEY_CLAUSE,
+    ALTERNATE_KEY_CLAUSE,
+    RELATIVE_KEY_CLAUSE,
+    FILE_STATUS_CLAUSE,
+    IDENTIFICATION_PARAGRAPH,
+
+    #[doc(hidden)]
+    __LAST,
 }
 
 use SyntaxKind::*;
 
+impl SyntaxKind {
+    /// Tokens that carry no meaning for the parser but are kept in the tree.
+    pub fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            WHITESPACE
+                | NEWLINE
+                | SEQUENCE_AREA
+                | INDICATOR
+                | IDENTIFICATION_AREA
+                | COMMENT
+                | COMMA
+                | SEMICOLON
+        )
+    }
+
+    pub fn is_usage(self) -> bool {
+        matches!(
+            self,
+            DISPLAY_KW
+                | COMP_KW
+                | COMP_1_KW
+                | COMP_2_KW
+                | COMP_3_KW
+                | COMP_5_KW
+                | BINARY_KW
+                | PACKED_DECIMAL_KW
+                | POINTER_KW
+                | INDEX_KW
+        )
+    }
+
+    /// Tokens that can start a literal, figurative constants included.
+    pub fn is_literal_start(self) -> bool {
+        matches!(
+            self,
+            STRING_LITERAL
+                | HEX_LITERAL
+                | NATIONAL_LITERAL
+                | NULL_TERMINATED_LITERAL
+                | INTEGER_LITERAL
+                | DECIMAL_LITERAL
+                | ALL_KW
+                | ZERO_KW
+                | SPACE_KW
+                | HIGH_VALUE_KW
+                | LOW_VALUE_KW
+                | QUOTE_KW
+                | NULL_KW
+        )
+    }
+}
+
+impl SyntaxKind {
+    pub fn is_statement(self) -> bool {
+        matches!(
+            self,
+            DISPLAY_STMT
+                | MOVE_STMT
+                | ADD_STMT
+                | SUBTRACT_STMT
+                | MULTIPLY_STMT
+                | DIVIDE_STMT
+                | COMPUTE_STMT
+                | IF_STMT
+                | EVALUATE_STMT
+                | PERFORM_STMT
+                | GO_TO_STMT
+                | EXIT_STMT
+                | STOP_STMT
+                | GOBACK_STMT
+                | CONTINUE_STMT
+                | NEXT_SENTENCE_STMT
+                | ALTER_STMT
+                | STRING_STMT
+                | UNSTRING_STMT
+                | INSPECT_STMT
+                | OPEN_STMT
+                | CLOSE_STMT
+                | READ_STMT
+                | WRITE_STMT
+                | REWRITE_STMT
+                | DELETE_STMT
+                | START_STMT
+                | CALL_STMT
+                | CANCEL_STMT
+                | ACCEPT_STMT
+                | SEARCH_STMT
+                | SET_STMT
+                | SORT_STMT
+                | MERGE_STMT
+                | RELEASE_STMT
+                | RETURN_STMT
+        )
+    }
+
+    /// Keywords that begin a statement.
+    pub fn is_verb(self) -> bool {
+        matches!(
+            self,
+            DISPLAY_KW
+                | MOVE_KW
+                | ADD_KW
+                | SUBTRACT_KW
+                | MULTIPLY_KW
+                | DIVIDE_KW
+                | COMPUTE_KW
+                | IF_KW
+                | EVALUATE_KW
+                | PERFORM_KW
+                | GO_KW
+                | EXIT_KW
+                | STOP_KW
+                | GOBACK_KW
+                | CONTINUE_KW
+                | NEXT_KW
+                | ALTER_KW
+                | STRING_KW
+                | UNSTRING_KW
+                | INSPECT_KW
+                | OPEN_KW
+                | CLOSE_KW
+                | READ_KW
+                | WRITE_KW
+                | REWRITE_KW
+                | DELETE_KW
+                | START_KW
+                | CALL_KW
+                | CANCEL_KW
+                | ACCEPT_KW
+                | SEARCH_KW
+                | SET_KW
+                | SORT_KW
+                | MERGE_KW
+                | RELEASE_KW
+                | RETURN_KW
+        )
+    }
+
+    /// Keywords that start an IDENTIFICATION DIVISION paragraph whose body
+    /// is a comment entry.
+    pub fn starts_comment_paragraph(self) -> bool {
+        matches!(
+            self,
+            AUTHOR_KW | INSTALLATION_KW | DATE_WRITTEN_KW | DATE_COMPILED_KW | SECURITY_KW | REMARKS_KW
+        )
+    }
+
+    pub fn is_division_header(self) -> bool {
+        matches!(self, IDENTIFICATION_KW | ENVIRONMENT_KW | DATA_KW | PROCEDURE_KW)
+    }
+}
+
 impl From<SyntaxKind> for rowan::SyntaxKind {
     fn from(kind: SyntaxKind) -> Self {
         Self(kind as u16)
@@ -48,7 +680,7 @@ impl rowan::Language for CobolLang {
     type Kind = SyntaxKind;
 
     fn kind_from_raw(raw: rowan::SyntaxKind) -> Self::Kind {
-        assert!(raw.0 <= DISPLAY_STMT as u16);
+        assert!(raw.0 < __LAST as u16);
         unsafe { std::mem::transmute::<u16, SyntaxKind>(raw.0) }
     }
 
@@ -67,63 +699,553 @@ pub type SyntaxElement = rowan::NodeOrToken<SyntaxNode, SyntaxToken>;
 // Lexer
 // ============================================================================
 
-fn lex(text: &str) -> Vec<(SyntaxKind, String)> {
-    let mut tokens = Vec::new();
-    let mut chars = text.char_indices().peekable();
-
-    while let Some((start, ch)) = chars.next() {
-        match ch {
-            '.' => tokens.push((DOT, ".".to_string())),
-            '"' => {
-                // String literal
-                let mut s = String::from("\"");
-                while let Some((_, c)) = chars.next() {
-                    s.push(c);
-                    if c == '"' {
-                        break;
-                    }
-                }
-                tokens.push((STRING_LITERAL, s));
+/// Reference format of the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SourceFormat {
+    /// Every column is program text.
+    #[default]
+    Free,
+    /// Columns 1-6 are the sequence area, column 7 the indicator area,
+    /// columns 8-72 program text (Area A and B) and anything past column 72
+    /// the identification area. A `*` or `/` indicator marks a comment line
+    /// and a `-` indicator a continuation line.
+    Fixed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceOptions {
+    pub format: SourceFormat,
+    /// Numeric literals use `,` as the decimal point. This is switched on by
+    /// a `DECIMAL-POINT IS COMMA` clause in the source as well.
+    pub decimal_point_is_comma: bool,
+}
+
+const SEQUENCE_AREA_END: usize = 6;
+const INDICATOR_COLUMN: usize = 7;
+const AREA_B_START: usize = 11;
+const PROGRAM_TEXT_END: usize = 72;
+
+fn lex(text: &str, options: SourceOptions) -> Vec<(SyntaxKind, String)> {
+    let mut lexer = Lexer {
+        format: options.format,
+        decimal_point_is_comma: options.decimal_point_is_comma,
+        ..Lexer::default()
+    };
+    match options.format {
+        SourceFormat::Free => lexer.lex_text(text),
+        SourceFormat::Fixed => {
+            for line in text.split_inclusive('\n') {
+                lexer.lex_fixed_line(line);
             }
-            '\n' => tokens.push((NEWLINE, "\n".to_string())),
-            c if c.is_whitespace() => {
-                let mut s = String::from(c);
-                while let Some(&(_, c)) = chars.peek() {
-                    if c.is_whitespace() && c != '\n' {
-                        s.push(c);
-                        chars.next();
-                    } else {
-                        break;
-                    }
-                }
-                tokens.push((WHITESPACE, s));
+        }
+    }
+    lexer.tokens
+}
+
+#[derive(Default)]
+struct Lexer {
+    format: SourceFormat,
+    tokens: Vec<(SyntaxKind, String)>,
+    /// The last significant token is an alphanumeric literal that ran off the
+    /// end of its line without a closing quote.
+    open_literal: bool,
+    decimal_point_is_comma: bool,
+    /// Inside the comment entries of an IDENTIFICATION DIVISION paragraph.
+    comment_entry: bool,
+}
+
+impl Lexer {
+    fn push(&mut self, kind: SyntaxKind, text: &str) {
+        if !text.is_empty() {
+            if !kind.is_trivia() {
+                self.open_literal = false;
             }
-            c if c.is_alphabetic() || c == '-' => {
-                let mut word = String::from(c);
-                while let Some(&(_, c)) = chars.peek() {
-                    if c.is_alphanumeric() || c == '-' {
-                        word.push(c);
-                        chars.next();
-                    } else {
-                        break;
-                    }
+            self.tokens.push((kind, text.to_string()));
+        }
+    }
+
+    /// Splits one fixed-form line into its areas. Only the program text is
+    /// lexed; the other areas become trivia so the tree stays lossless.
+    fn lex_fixed_line(&mut self, line: &str) {
+        let (line, newline) = match line.strip_suffix("\r\n") {
+            Some(l) => (l, "\r\n"),
+            None => match line.strip_suffix('\n') {
+                Some(l) => (l, "\n"),
+                None => (line, ""),
+            },
+        };
+        let column = |col: usize| {
+            line.char_indices()
+                .nth(col)
+                .map_or(line.len(), |(offset, _)| offset)
+        };
+        let sequence_end = column(SEQUENCE_AREA_END);
+        let indicator_end = column(INDICATOR_COLUMN);
+        let text_end = column(PROGRAM_TEXT_END);
+
+        self.push(SEQUENCE_AREA, &line[..sequence_end]);
+        let indicator = &line[sequence_end..indicator_end];
+        self.push(INDICATOR, indicator);
+        if indicator != "-" {
+            // Only a continuation line may resume an open literal
+            self.open_literal = false;
+        }
+        if matches!(indicator, "*" | "/") {
+            self.push(COMMENT, &line[indicator_end..text_end]);
+        } else {
+            self.lex_text(&line[indicator_end..text_end]);
+        }
+        self.push(IDENTIFICATION_AREA, &line[text_end..]);
+        self.push(NEWLINE, newline);
+    }
+
+    fn decimal_point(&self) -> char {
+        if self.decimal_point_is_comma { ',' } else { '.' }
+    }
+
+    /// Whether `rest` starts with a PICTURE character-string, i.e. follows
+    /// `PIC` or `PICTURE IS`. Picture strings contain periods, commas and
+    /// parentheses, so they cannot be lexed as ordinary words.
+    fn expects_picture_string(&self, rest: &str) -> bool {
+        let mut significant = self.tokens.iter().rev().map(|(k, _)| *k).filter(|k| !k.is_trivia());
+        match significant.next() {
+            Some(PICTURE_KW) => {
+                let word = scan_while(rest, |c| !c.is_whitespace());
+                !rest[..word].eq_ignore_ascii_case("IS")
+            }
+            Some(IS_KW) => significant.next() == Some(PICTURE_KW),
+            _ => false,
+        }
+    }
+
+    /// Whether the DOT just pushed ends an `AUTHOR.`-style paragraph header.
+    fn follows_comment_paragraph_header(&self) -> bool {
+        let mut significant = self.tokens.iter().rev().map(|(k, _)| *k).filter(|k| !k.is_trivia());
+        significant.next();
+        significant.next().is_some_and(SyntaxKind::starts_comment_paragraph)
+    }
+
+    /// Whether the COMMA just pushed completes `DECIMAL-POINT IS COMMA`.
+    fn follows_decimal_point_clause(&self) -> bool {
+        let mut significant = self.tokens.iter().rev().map(|(k, _)| *k).filter(|k| !k.is_trivia());
+        significant.next();
+        match significant.next() {
+            Some(DECIMAL_POINT_KW) => true,
+            Some(IS_KW) => significant.next() == Some(DECIMAL_POINT_KW),
+            _ => false,
+        }
+    }
+
+    /// Whether nothing but layout precedes the current position on its line.
+    fn at_line_start(&self) -> bool {
+        self.tokens
+            .iter()
+            .rev()
+            .map(|(k, _)| *k)
+            .find(|k| !matches!(k, WHITESPACE | SEQUENCE_AREA | INDICATOR))
+            .is_none_or(|k| k == NEWLINE)
+    }
+
+    /// Whether the text at `start` continues a comment entry. Comment entries
+    /// run until a line starts with the next header: `<word> DIVISION`,
+    /// `PROGRAM-ID` or a comment paragraph name followed by a period. In fixed
+    /// format the header must begin in Area A.
+    fn continues_comment_entry(&self, text: &str, start: usize) -> bool {
+        if !self.comment_entry {
+            return false;
+        }
+        if !self.at_line_start() {
+            return true;
+        }
+        if self.format == SourceFormat::Fixed && text[..start].chars().count() >= AREA_B_START - INDICATOR_COLUMN {
+            return true;
+        }
+        let rest = &text[start..];
+        let word = scan_while(rest, |c| c.is_alphanumeric() || c == '-');
+        let after = rest[word..].trim_start_matches([' ', '\t']);
+        let header = keyword(&rest[..word].to_uppercase()).is_some_and(|k| match k {
+            PROGRAM_ID_KW => true,
+            k if k.is_division_header() => {
+                let next = scan_while(after, |c| c.is_alphanumeric() || c == '-');
+                after[..next].eq_ignore_ascii_case("DIVISION")
+            }
+            k => k.starts_comment_paragraph() && after.starts_with('.'),
+        });
+        !header
+    }
+
+    fn lex_text(&mut self, text: &str) {
+        let mut start = 0;
+        while let Some(ch) = text[start..].chars().next() {
+            let rest = &text[start..];
+            let mut open_literal = false;
+            let number = scan_number(rest, self.decimal_point());
+            let (kind, len) = match ch {
+                c if !c.is_whitespace() && self.continues_comment_entry(text, start) => {
+                    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+                    (COMMENT_ENTRY, line.trim_end().len())
                 }
-                let upper = word.to_uppercase();
-                let kind = match upper.as_str() {
-                    "IDENTIFICATION" => IDENTIFICATION_KW,
-                    "DIVISION" => DIVISION_KW,
-                    "PROGRAM-ID" => PROGRAM_ID_KW,
-                    "PROCEDURE" => PROCEDURE_KW,
-                    "DISPLAY" => DISPLAY_KW,
-                    _ => IDENT,
-                };
-                tokens.push((kind, word));
+                c if !c.is_whitespace() && self.expects_picture_string(rest) => {
+                    (PICTURE_STRING, scan_picture_string(rest))
+                }
+                _ if number.is_some() => number.unwrap(),
+                '*' if rest.starts_with("*>") => {
+                    // Floating comment, runs to the end of the line
+                    (COMMENT, rest.find('\n').unwrap_or(rest.len()))
+                }
+                '*' if rest.starts_with("**") => (POWER, 2),
+                '*' => (STAR, 1),
+                '/' => (SLASH, 1),
+                '+' => (PLUS, 1),
+                '-' => (MINUS, 1),
+                '=' => (EQUALS, 1),
+                '>' if rest.starts_with(">=") => (GREATER_EQUALS, 2),
+                '>' => (GREATER, 1),
+                '<' if rest.starts_with("<=") => (LESS_EQUALS, 2),
+                '<' => (LESS, 1),
+                '.' => (DOT, 1),
+                ',' => (COMMA, 1),
+                ';' => (SEMICOLON, 1),
+                ':' => (COLON, 1),
+                '(' => (L_PAREN, 1),
+                ')' => (R_PAREN, 1),
+                '"' | '\'' => {
+                    // An unterminated literal stops at the end of the line and
+                    // may be resumed by a continuation line
+                    let kind = if self.open_literal { LITERAL_CONTINUATION } else { STRING_LITERAL };
+                    let (len, terminated) = scan_string(rest);
+                    open_literal = !terminated;
+                    (kind, len)
+                }
+                'X' | 'x' | 'N' | 'n' | 'Z' | 'z' if rest[1..].starts_with(['"', '\'']) => {
+                    let kind = match ch.to_ascii_uppercase() {
+                        'X' => HEX_LITERAL,
+                        'N' => NATIONAL_LITERAL,
+                        _ => NULL_TERMINATED_LITERAL,
+                    };
+                    let (len, terminated) = scan_string(&rest[1..]);
+                    open_literal = !terminated;
+                    (kind, 1 + len)
+                }
+                '\n' => (NEWLINE, 1),
+                c if c.is_whitespace() => {
+                    (WHITESPACE, scan_while(rest, |c| c.is_whitespace() && c != '\n'))
+                }
+                c if c.is_alphanumeric() => {
+                    let len = scan_while(rest, |c| c.is_alphanumeric() || c == '-');
+                    (keyword(&rest[..len].to_uppercase()).unwrap_or(IDENT), len)
+                }
+                _ => (ERROR, ch.len_utf8()),
+            };
+            self.push(kind, &rest[..len]);
+            self.open_literal |= open_literal;
+            if kind == NEWLINE && self.format == SourceFormat::Free {
+                // Literals are only continued in fixed format
+                self.open_literal = false;
+            }
+            if kind == DOT {
+                self.comment_entry = self.follows_comment_paragraph_header();
+            } else if !kind.is_trivia() && kind != COMMENT_ENTRY {
+                self.comment_entry = false;
             }
-            _ => tokens.push((ERROR, ch.to_string())),
+            if kind == COMMA_KW && self.follows_decimal_point_clause() {
+                // Numeric literals after SPECIAL-NAMES use the comma
+                self.decimal_point_is_comma = true;
+            }
+            start += len;
+        }
+    }
+}
+
+fn keyword(word: &str) -> Option<SyntaxKind> {
+    let kind = match word {
+        "IDENTIFICATION" | "ID" => IDENTIFICATION_KW,
+        "DIVISION" => DIVISION_KW,
+        "PROGRAM-ID" => PROGRAM_ID_KW,
+        "PROCEDURE" => PROCEDURE_KW,
+        "DISPLAY" => DISPLAY_KW,
+        "DATA" => DATA_KW,
+        "SECTION" => SECTION_KW,
+        "FILE" => FILE_KW,
+        "WORKING-STORAGE" => WORKING_STORAGE_KW,
+        "LOCAL-STORAGE" => LOCAL_STORAGE_KW,
+        "LINKAGE" => LINKAGE_KW,
+        "FD" => FD_KW,
+        "FILLER" => FILLER_KW,
+        "PIC" | "PICTURE" => PICTURE_KW,
+        "IS" => IS_KW,
+        "USAGE" => USAGE_KW,
+        "COMP" | "COMPUTATIONAL" | "COMP-4" | "COMPUTATIONAL-4" => COMP_KW,
+        "COMP-1" | "COMPUTATIONAL-1" => COMP_1_KW,
+        "COMP-2" | "COMPUTATIONAL-2" => COMP_2_KW,
+        "COMP-3" | "COMPUTATIONAL-3" => COMP_3_KW,
+        "COMP-5" | "COMPUTATIONAL-5" => COMP_5_KW,
+        "BINARY" => BINARY_KW,
+        "PACKED-DECIMAL" => PACKED_DECIMAL_KW,
+        "POINTER" => POINTER_KW,
+        "INDEX" => INDEX_KW,
+        "VALUE" | "VALUES" => VALUE_KW,
+        "ARE" => ARE_KW,
+        "THRU" | "THROUGH" => THRU_KW,
+        "OCCURS" => OCCURS_KW,
+        "TO" => TO_KW,
+        "TIMES" => TIMES_KW,
+        "DEPENDING" => DEPENDING_KW,
+        "ON" => ON_KW,
+        "ASCENDING" => ASCENDING_KW,
+        "DESCENDING" => DESCENDING_KW,
+        "KEY" => KEY_KW,
+        "INDEXED" => INDEXED_KW,
+        "BY" => BY_KW,
+        "REDEFINES" => REDEFINES_KW,
+        "RENAMES" => RENAMES_KW,
+        "JUST" | "JUSTIFIED" => JUSTIFIED_KW,
+        "RIGHT" => RIGHT_KW,
+        "LEFT" => LEFT_KW,
+        "SIGN" => SIGN_KW,
+        "LEADING" => LEADING_KW,
+        "TRAILING" => TRAILING_KW,
+        "SEPARATE" => SEPARATE_KW,
+        "CHARACTER" => CHARACTER_KW,
+        "BLANK" => BLANK_KW,
+        "WHEN" => WHEN_KW,
+        "SYNC" | "SYNCHRONIZED" => SYNCHRONIZED_KW,
+        "ENVIRONMENT" => ENVIRONMENT_KW,
+        "CONFIGURATION" => CONFIGURATION_KW,
+        "SOURCE-COMPUTER" => SOURCE_COMPUTER_KW,
+        "OBJECT-COMPUTER" => OBJECT_COMPUTER_KW,
+        "SPECIAL-NAMES" => SPECIAL_NAMES_KW,
+        "DECIMAL-POINT" => DECIMAL_POINT_KW,
+        "COMMA" => COMMA_KW,
+        "INPUT-OUTPUT" => INPUT_OUTPUT_KW,
+        "FILE-CONTROL" => FILE_CONTROL_KW,
+        "I-O-CONTROL" => I_O_CONTROL_KW,
+        "SELECT" => SELECT_KW,
+        "OPTIONAL" => OPTIONAL_KW,
+        "ASSIGN" => ASSIGN_KW,
+        "ORGANIZATION" => ORGANIZATION_KW,
+        "SEQUENTIAL" => SEQUENTIAL_KW,
+        "RELATIVE" => RELATIVE_KW,
+        "LINE" | "LINES" => LINE_KW,
+        "ACCESS" => ACCESS_KW,
+        "MODE" => MODE_KW,
+        "RANDOM" => RANDOM_KW,
+        "DYNAMIC" => DYNAMIC_KW,
+        "RECORD" => RECORD_KW,
+        "ALTERNATE" => ALTERNATE_KW,
+        "WITH" => WITH_KW,
+        "DUPLICATES" => DUPLICATES_KW,
+        "STATUS" => STATUS_KW,
+        "AUTHOR" => AUTHOR_KW,
+        "INSTALLATION" => INSTALLATION_KW,
+        "DATE-WRITTEN" => DATE_WRITTEN_KW,
+        "DATE-COMPILED" => DATE_COMPILED_KW,
+        "SECURITY" => SECURITY_KW,
+        "REMARKS" => REMARKS_KW,
+        "PROGRAM" => PROGRAM_KW,
+        "INITIAL" => INITIAL_KW,
+        "COMMON" => COMMON_KW,
+        "RECURSIVE" => RECURSIVE_KW,
+        "MOVE" => MOVE_KW,
+        "CORR" | "CORRESPONDING" => CORRESPONDING_KW,
+        "ADD" => ADD_KW,
+        "SUBTRACT" => SUBTRACT_KW,
+        "MULTIPLY" => MULTIPLY_KW,
+        "DIVIDE" => DIVIDE_KW,
+        "COMPUTE" => COMPUTE_KW,
+        "FROM" => FROM_KW,
+        "INTO" => INTO_KW,
+        "GIVING" => GIVING_KW,
+        "REMAINDER" => REMAINDER_KW,
+        "ROUNDED" => ROUNDED_KW,
+        "SIZE" => SIZE_KW,
+        "ERROR" => ERROR_KW,
+        "NOT" => NOT_KW,
+        "EQUAL" => EQUAL_KW,
+        "END-ADD" => END_ADD_KW,
+        "END-SUBTRACT" => END_SUBTRACT_KW,
+        "END-MULTIPLY" => END_MULTIPLY_KW,
+        "END-DIVIDE" => END_DIVIDE_KW,
+        "END-COMPUTE" => END_COMPUTE_KW,
+        "IF" => IF_KW,
+        "THEN" => THEN_KW,
+        "ELSE" => ELSE_KW,
+        "END-IF" => END_IF_KW,
+        "AND" => AND_KW,
+        "OR" => OR_KW,
+        "GREATER" => GREATER_KW,
+        "LESS" => LESS_KW,
+        "THAN" => THAN_KW,
+        "NUMERIC" => NUMERIC_KW,
+        "ALPHABETIC" => ALPHABETIC_KW,
+        "ALPHABETIC-LOWER" => ALPHABETIC_LOWER_KW,
+        "ALPHABETIC-UPPER" => ALPHABETIC_UPPER_KW,
+        "POSITIVE" => POSITIVE_KW,
+        "NEGATIVE" => NEGATIVE_KW,
+        "EVALUATE" => EVALUATE_KW,
+        "ALSO" => ALSO_KW,
+        "OTHER" => OTHER_KW,
+        "ANY" => ANY_KW,
+        "TRUE" => TRUE_KW,
+        "FALSE" => FALSE_KW,
+        "END-EVALUATE" => END_EVALUATE_KW,
+        "PERFORM" => PERFORM_KW,
+        "UNTIL" => UNTIL_KW,
+        "TEST" => TEST_KW,
+        "BEFORE" => BEFORE_KW,
+        "AFTER" => AFTER_KW,
+        "VARYING" => VARYING_KW,
+        "END-PERFORM" => END_PERFORM_KW,
+        "GO" => GO_KW,
+        "EXIT" => EXIT_KW,
+        "STOP" => STOP_KW,
+        "RUN" => RUN_KW,
+        "GOBACK" => GOBACK_KW,
+        "CONTINUE" => CONTINUE_KW,
+        "NEXT" => NEXT_KW,
+        "SENTENCE" => SENTENCE_KW,
+        "ALTER" => ALTER_KW,
+        "PROCEED" => PROCEED_KW,
+        "PARAGRAPH" => PARAGRAPH_KW,
+        "CYCLE" => CYCLE_KW,
+        "STRING" => STRING_KW,
+        "DELIMITED" => DELIMITED_KW,
+        "OVERFLOW" => OVERFLOW_KW,
+        "END-STRING" => END_STRING_KW,
+        "UNSTRING" => UNSTRING_KW,
+        "DELIMITER" => DELIMITER_KW,
+        "COUNT" => COUNT_KW,
+        "IN" => IN_KW,
+        "TALLYING" => TALLYING_KW,
+        "END-UNSTRING" => END_UNSTRING_KW,
+        "INSPECT" => INSPECT_KW,
+        "FOR" => FOR_KW,
+        "CHARACTERS" => CHARACTERS_KW,
+        "FIRST" => FIRST_KW,
+        "REPLACING" => REPLACING_KW,
+        "CONVERTING" => CONVERTING_KW,
+        "OPEN" => OPEN_KW,
+        "CLOSE" => CLOSE_KW,
+        "READ" => READ_KW,
+        "WRITE" => WRITE_KW,
+        "REWRITE" => REWRITE_KW,
+        "DELETE" => DELETE_KW,
+        "START" => START_KW,
+        "INPUT" => INPUT_KW,
+        "OUTPUT" => OUTPUT_KW,
+        "I-O" => I_O_KW,
+        "EXTEND" => EXTEND_KW,
+        "END" => END_KW,
+        "AT" => AT_KW,
+        "INVALID" => INVALID_KW,
+        "PREVIOUS" => PREVIOUS_KW,
+        "ADVANCING" => ADVANCING_KW,
+        "PAGE" => PAGE_KW,
+        "END-OF-PAGE" | "EOP" => END_OF_PAGE_KW,
+        "REEL" => REEL_KW,
+        "UNIT" => UNIT_KW,
+        "REMOVAL" => REMOVAL_KW,
+        "NO" => NO_KW,
+        "REWIND" => REWIND_KW,
+        "REVERSED" => REVERSED_KW,
+        "LOCK" => LOCK_KW,
+        "END-READ" => END_READ_KW,
+        "END-WRITE" => END_WRITE_KW,
+        "END-REWRITE" => END_REWRITE_KW,
+        "END-DELETE" => END_DELETE_KW,
+        "END-START" => END_START_KW,
+        "CALL" => CALL_KW,
+        "CANCEL" => CANCEL_KW,
+        "USING" => USING_KW,
+        "REFERENCE" => REFERENCE_KW,
+        "CONTENT" => CONTENT_KW,
+        "RETURNING" => RETURNING_KW,
+        "EXCEPTION" => EXCEPTION_KW,
+        "OMITTED" => OMITTED_KW,
+        "END-CALL" => END_CALL_KW,
+        "ACCEPT" => ACCEPT_KW,
+        "UPON" => UPON_KW,
+        "DATE" => DATE_KW,
+        "DAY" => DAY_KW,
+        "DAY-OF-WEEK" => DAY_OF_WEEK_KW,
+        "TIME" => TIME_KW,
+        "SEARCH" => SEARCH_KW,
+        "END-SEARCH" => END_SEARCH_KW,
+        "SET" => SET_KW,
+        "UP" => UP_KW,
+        "DOWN" => DOWN_KW,
+        "ADDRESS" => ADDRESS_KW,
+        "OF" => OF_KW,
+        "SORT" => SORT_KW,
+        "MERGE" => MERGE_KW,
+        "RELEASE" => RELEASE_KW,
+        "RETURN" => RETURN_KW,
+        "END-RETURN" => END_RETURN_KW,
+        "SD" => SD_KW,
+        "COLLATING" => COLLATING_KW,
+        "SEQUENCE" => SEQUENCE_KW,
+        "ORDER" => ORDER_KW,
+        "FUNCTION" => FUNCTION_KW,
+        "ALL" => ALL_KW,
+        "ZERO" | "ZEROS" | "ZEROES" => ZERO_KW,
+        "SPACE" | "SPACES" => SPACE_KW,
+        "HIGH-VALUE" | "HIGH-VALUES" => HIGH_VALUE_KW,
+        "LOW-VALUE" | "LOW-VALUES" => LOW_VALUE_KW,
+        "QUOTE" | "QUOTES" => QUOTE_KW,
+        "NULL" | "NULLS" => NULL_KW,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+fn scan_while(text: &str, pred: impl Fn(char) -> bool) -> usize {
+    text.find(|c| !pred(c)).unwrap_or(text.len())
+}
+
+/// Length of the picture string at the start of `text`. A trailing period,
+/// comma or semicolon is a separator, not part of the picture.
+fn scan_picture_string(text: &str) -> usize {
+    let len = scan_while(text, |c| !c.is_whitespace());
+    if len > 1 && text[..len].ends_with(['.', ',', ';']) { len - 1 } else { len }
+}
+
+/// Length of the quoted literal at the start of `text` and whether its
+/// closing quote was found before the end of the line. A doubled quote
+/// stands for one quote character inside the literal.
+fn scan_string(text: &str) -> (usize, bool) {
+    let quote = text.chars().next().unwrap();
+    let mut pos = 1;
+    while let Some(i) = text[pos..].find([quote, '\n']) {
+        pos += i;
+        if text[pos..].starts_with('\n') {
+            return (pos, false);
+        }
+        if text[pos + 1..].starts_with(quote) {
+            pos += 2;
+        } else {
+            return (pos + 1, true);
         }
     }
+    (text.len(), false)
+}
 
-    tokens
+/// Recognises an integer or decimal literal with an optional sign at the
+/// start of `text`. Digits running into a word, as in `1000-INIT`, are not a
+/// literal.
+fn scan_number(text: &str, decimal_point: char) -> Option<(SyntaxKind, usize)> {
+    let sign = usize::from(text.starts_with(['+', '-']));
+    let integer = scan_while(&text[sign..], |c| c.is_ascii_digit());
+    let len = sign + integer;
+    if let Some(after) = text[len..].strip_prefix(decimal_point) {
+        let fraction = scan_while(after, |c| c.is_ascii_digit());
+        if fraction > 0 {
+            return Some((DECIMAL_LITERAL, len + 1 + fraction));
+        }
+    }
+    if integer == 0 || text[len..].starts_with(|c: char| c.is_alphanumeric() || c == '-') {
+        return None;
+    }
+    Some((INTEGER_LITERAL, len))
 }
 
 // ============================================================================
@@ -133,6 +1255,8 @@ fn lex(text: &str) -> Vec<(SyntaxKind, String)> {
 pub struct Parse {
     green_node: GreenNode,
     pub errors: Vec<String>,
+    /// Valid constructs that defeat analysis, such as ALTER.
+    pub warnings: Vec<String>,
 }
 
 impl Parse {
@@ -146,6 +1270,14 @@ struct Parser {
     pos: usize,
     builder: GreenNodeBuilder<'static>,
     errors: Vec<String>,
+    warnings: Vec<String>,
+    /// A relation condition has been parsed in the current condition, so
+    /// abbreviated relations may follow.
+    relation_seen: bool,
+    /// Level 88 names of the DATA DIVISION parsed so far, in upper case.
+    condition_names: HashSet<String>,
+    /// `DECIMAL-POINT IS COMMA` is in effect for PICTURE strings.
+    decimal_point_is_comma: bool,
 }
 
 impl Parser {
@@ -155,6 +1287,10 @@ impl Parser {
             pos: 0,
             builder: GreenNodeBuilder::new(),
             errors: Vec::new(),
+            warnings: Vec::new(),
+            relation_seen: false,
+            condition_names: HashSet::new(),
+            decimal_point_is_comma: false,
         }
     }
 
@@ -174,9 +1310,85 @@ impl Parser {
     }
 
     fn skip_ws(&mut self) {
-        while matches!(self.current(), Some(WHITESPACE) | Some(NEWLINE)) {
+        while self.current().is_some_and(SyntaxKind::is_trivia) {
+            self.bump();
+        }
+    }
+
+    /// Kind of the next token that is not trivia, without consuming anything.
+    fn peek_significant(&self) -> Option<SyntaxKind> {
+        self.nth_significant(0)
+    }
+
+    /// Kind of the `n`th upcoming token that is not trivia.
+    fn nth_significant(&self, n: usize) -> Option<SyntaxKind> {
+        self.tokens[self.pos..]
+            .iter()
+            .map(|(k, _)| *k)
+            .filter(|k| !k.is_trivia())
+            .nth(n)
+    }
+
+    fn at(&self, kind: SyntaxKind) -> bool {
+        self.peek_significant() == Some(kind)
+    }
+
+    /// Bumps the next significant token if it is `kind`, along with the
+    /// trivia before it.
+    fn eat(&mut self, kind: SyntaxKind) -> bool {
+        if self.at(kind) {
+            self.skip_ws();
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the next significant token if it is the context word `word`,
+    /// an identifier that only has a meaning in this position.
+    fn eat_word(&mut self, word: &str) -> bool {
+        let next = self.tokens[self.pos..].iter().find(|(k, _)| !k.is_trivia());
+        if next.is_some_and(|(k, t)| *k == IDENT && t.eq_ignore_ascii_case(word)) {
+            self.skip_ws();
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Bumps a literal together with the fragments resumed on continuation
+    /// lines.
+    fn bump_literal(&mut self) {
+        let mut last = self.current_text().unwrap_or_default().to_string();
+        self.bump();
+        while self.peek_significant() == Some(LITERAL_CONTINUATION) {
+            self.skip_ws();
+            last = self.current_text().unwrap_or_default().to_string();
+            self.bump();
+        }
+        if let Some(quoted) = last.find(['"', '\'']).map(|i| &last[i..])
+            && !scan_string(quoted).1
+        {
+            self.errors.push(format!("Unterminated literal {}", last.trim_end()));
+        }
+    }
+
+    /// Parses a literal or figurative constant, optionally preceded by ALL.
+    fn parse_literal(&mut self) {
+        self.builder.start_node(LITERAL.into());
+        if self.current() == Some(ALL_KW) {
             self.bump();
+            self.skip_ws();
+            if !self.current().is_some_and(|k| k.is_literal_start() && k != ALL_KW) {
+                self.errors.push("Expected literal after ALL".to_string());
+                self.builder.finish_node();
+                return;
+            }
         }
+        self.bump_literal();
+        self.builder.finish_node();
     }
 
     fn expect(&mut self, kind: SyntaxKind) -> bool {
@@ -189,21 +1401,47 @@ impl Parser {
         }
     }
 
+    /// Whether the parenthesis at the next significant token encloses a
+    /// reference modifier `(start:length)` rather than subscripts.
+    fn at_ref_mod(&self) -> bool {
+        let mut depth = 0;
+        for kind in self.tokens[self.pos..].iter().map(|(k, _)| *k).filter(|k| !k.is_trivia()) {
+            match kind {
+                L_PAREN => depth += 1,
+                R_PAREN if depth == 1 => return false,
+                R_PAREN => depth -= 1,
+                COLON if depth == 1 => return true,
+                DOT => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
     fn parse(mut self) -> Parse {
         self.builder.start_node(ROOT.into());
 
         self.skip_ws();
 
-        // Parse IDENTIFICATION DIVISION.
+        // Parse IDENTIFICATION DIVISION, or a bare PROGRAM-ID paragraph.
         if self.current() == Some(IDENTIFICATION_KW) {
             self.parse_identification_division();
+        } else if self.current() == Some(PROGRAM_ID_KW) {
+            self.parse_program_id();
         }
 
         self.skip_ws();
 
-        // Parse PROGRAM-ID. <name>.
-        if self.current() == Some(PROGRAM_ID_KW) {
-            self.parse_program_id();
+        // Parse ENVIRONMENT DIVISION.
+        if self.current() == Some(ENVIRONMENT_KW) {
+            self.parse_environment_division();
+        }
+
+        self.skip_ws();
+
+        // Parse DATA DIVISION.
+        if self.current() == Some(DATA_KW) {
+            self.parse_data_division();
         }
 
         self.skip_ws();
@@ -213,223 +1451,6983 @@ impl Parser {
             self.parse_procedure_division();
         }
 
+        // Keep whatever could not be parsed so the tree stays lossless
+        if self.current().is_some() {
+            self.errors.push(format!("Unexpected {:?}", self.current()));
+            while self.current().is_some() {
+                self.bump();
+            }
+        }
+
         self.builder.finish_node();
         Parse {
             green_node: self.builder.finish(),
             errors: self.errors,
+            warnings: self.warnings,
         }
     }
 
     fn parse_identification_division(&mut self) {
         self.builder.start_node(IDENTIFICATION_DIVISION.into());
-        self.bump(); // IDENTIFICATION
+        self.bump(); // IDENTIFICATION or ID
         self.skip_ws();
         self.expect(DIVISION_KW);
         self.expect(DOT);
+
+        loop {
+            match self.peek_significant() {
+                Some(PROGRAM_ID_KW) => {
+                    self.skip_ws();
+                    self.parse_program_id();
+                }
+                Some(k) if k.starts_comment_paragraph() => {
+                    self.skip_ws();
+                    self.parse_comment_paragraph();
+                }
+                _ => break,
+            }
+        }
+
         self.builder.finish_node();
     }
 
+    /// `PROGRAM-ID. name [IS] [INITIAL | COMMON | RECURSIVE] [PROGRAM].`
     fn parse_program_id(&mut self) {
         self.builder.start_node(PROGRAM_ID_CLAUSE.into());
         self.bump(); // PROGRAM-ID
         self.expect(DOT);
         self.skip_ws();
-        if self.current() == Some(IDENT) {
-            self.bump(); // program name
-        } else {
-            self.errors.push("Expected program name".to_string());
+        match self.current() {
+            Some(IDENT) => self.bump(), // program name
+            Some(STRING_LITERAL) => self.parse_literal(),
+            _ => self.errors.push("Expected program name".to_string()),
         }
+        self.eat(IS_KW);
+        while self.eat(INITIAL_KW) || self.eat(COMMON_KW) || self.eat(RECURSIVE_KW) {}
+        self.eat(PROGRAM_KW);
+        self.skip_ws();
         self.expect(DOT);
         self.builder.finish_node();
     }
 
-    fn parse_procedure_division(&mut self) {
-        self.builder.start_node(PROCEDURE_DIVISION.into());
-        self.bump(); // PROCEDURE
-        self.skip_ws();
-        self.expect(DIVISION_KW);
+    /// `AUTHOR.` and the like, followed by free-form comment entries.
+    fn parse_comment_paragraph(&mut self) {
+        self.builder.start_node(IDENTIFICATION_PARAGRAPH.into());
+        self.bump(); // paragraph name
         self.expect(DOT);
+        while self.at(COMMENT_ENTRY) {
+            self.skip_ws();
+            self.bump();
+        }
+        self.builder.finish_node();
+    }
 
+    fn parse_environment_division(&mut self) {
+        self.builder.start_node(ENVIRONMENT_DIVISION.into());
+        self.bump(); // ENVIRONMENT
         self.skip_ws();
+        self.expect(DIVISION_KW);
+        self.expect(DOT);
 
-        // Parse statements
-        while self.pos < self.tokens.len() {
+        loop {
             self.skip_ws();
-            if self.current() == Some(DISPLAY_KW) {
-                self.parse_display_stmt();
-            } else if self.current().is_some() {
-                // Unknown token, skip
-                self.bump();
-            } else {
-                break;
+            match self.current() {
+                Some(CONFIGURATION_KW) => self.parse_configuration_section(),
+                Some(INPUT_OUTPUT_KW) => self.parse_input_output_section(),
+                _ if self.at_environment_boundary() => break,
+                found => {
+                    self.errors.push(format!("Expected section header, found {:?}", found));
+                    self.bump();
+                }
             }
         }
 
         self.builder.finish_node();
     }
 
-    fn parse_display_stmt(&mut self) {
-        self.builder.start_node(DISPLAY_STMT.into());
-        self.bump(); // DISPLAY
+    /// Start of the next paragraph, section or division, or end of input.
+    fn at_environment_boundary(&self) -> bool {
+        matches!(
+            self.peek_significant(),
+            None | Some(
+                CONFIGURATION_KW
+                    | SOURCE_COMPUTER_KW
+                    | OBJECT_COMPUTER_KW
+                    | SPECIAL_NAMES_KW
+                    | INPUT_OUTPUT_KW
+                    | FILE_CONTROL_KW
+                    | I_O_CONTROL_KW
+                    | DATA_KW
+                    | PROCEDURE_KW
+            )
+        )
+    }
+
+    fn parse_section_header(&mut self) {
+        self.bump(); // section name
         self.skip_ws();
-        if self.current() == Some(STRING_LITERAL) {
-            self.bump();
-        } else {
-            self.errors.push("Expected string literal after DISPLAY".to_string());
-        }
-        // Optional dot
-        if self.current() == Some(DOT) {
-            self.bump();
+        self.expect(SECTION_KW);
+        self.expect(DOT);
+    }
+
+    fn parse_configuration_section(&mut self) {
+        self.builder.start_node(CONFIGURATION_SECTION.into());
+        self.parse_section_header();
+        loop {
+            self.skip_ws();
+            match self.current() {
+                Some(SOURCE_COMPUTER_KW) => self.parse_environment_paragraph(SOURCE_COMPUTER_PARAGRAPH),
+                Some(OBJECT_COMPUTER_KW) => self.parse_environment_paragraph(OBJECT_COMPUTER_PARAGRAPH),
+                Some(SPECIAL_NAMES_KW) => self.parse_special_names(),
+                _ => break,
+            }
         }
         self.builder.finish_node();
     }
-}
-
-pub fn parse(text: &str) -> Parse {
-    let tokens = lex(text);
-    Parser::new(tokens).parse()
-}
 
-// ============================================================================
-// AST wrappers
-// ============================================================================
+    /// A paragraph whose body is kept as plain tokens up to its period, such
+    /// as `SOURCE-COMPUTER. IBM-370.`
+    fn parse_environment_paragraph(&mut self, kind: SyntaxKind) {
+        self.builder.start_node(kind.into());
+        self.bump(); // paragraph name
+        self.expect(DOT);
+        self.parse_paragraph_body();
+        self.builder.finish_node();
+    }
 
-macro_rules! ast_node {
-    ($ast:ident, $kind:ident) => {
-        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-        #[repr(transparent)]
-        pub struct $ast(SyntaxNode);
+    fn parse_paragraph_body(&mut self) {
+        if self.at_environment_boundary() {
+            return;
+        }
+        while !self.at(DOT) && !self.at_environment_boundary() {
+            self.skip_ws();
+            self.bump();
+        }
+        self.skip_ws();
+        self.expect(DOT);
+    }
 
-        impl $ast {
-            pub fn cast(node: SyntaxNode) -> Option<Self> {
-                if node.kind() == $kind {
-                    Some(Self(node))
-                } else {
-                    None
+    fn parse_special_names(&mut self) {
+        self.builder.start_node(SPECIAL_NAMES_PARAGRAPH.into());
+        self.bump(); // SPECIAL-NAMES
+        self.expect(DOT);
+        while !self.at(DOT) && !self.at_environment_boundary() {
+            self.skip_ws();
+            match self.current() {
+                Some(DECIMAL_POINT_KW) => {
+                    self.builder.start_node(DECIMAL_POINT_CLAUSE.into());
+                    self.bump();
+                    self.eat(IS_KW);
+                    if self.eat(COMMA_KW) {
+                        self.decimal_point_is_comma = true;
+                    } else {
+                        self.errors.push("Expected COMMA after DECIMAL-POINT IS".to_string());
+                    }
+                    self.builder.finish_node();
                 }
+                Some(IDENT) if self.nth_significant(1) == Some(IS_KW) => {
+                    // environment-name IS mnemonic-name
+                    self.builder.start_node(SPECIAL_NAME_CLAUSE.into());
+                    self.bump();
+                    self.eat(IS_KW);
+                    self.skip_ws();
+                    self.expect(IDENT);
+                    self.builder.finish_node();
+                }
+                _ => self.bump(),
             }
+        }
+        if !self.at_environment_boundary() {
+            self.skip_ws();
+            self.expect(DOT);
+        }
+        self.builder.finish_node();
+    }
 
-            pub fn syntax(&self) -> &SyntaxNode {
-                &self.0
+    fn parse_input_output_section(&mut self) {
+        self.builder.start_node(INPUT_OUTPUT_SECTION.into());
+        self.parse_section_header();
+        loop {
+            self.skip_ws();
+            match self.current() {
+                Some(FILE_CONTROL_KW) => self.parse_file_control(),
+                Some(I_O_CONTROL_KW) => self.parse_environment_paragraph(I_O_CONTROL_PARAGRAPH),
+                _ => break,
             }
         }
-    };
-}
-
-ast_node!(Root, ROOT);
-ast_node!(IdentificationDivision, IDENTIFICATION_DIVISION);
-ast_node!(ProgramIdClause, PROGRAM_ID_CLAUSE);
-ast_node!(ProcedureDivision, PROCEDURE_DIVISION);
-ast_node!(DisplayStmt, DISPLAY_STMT);
-
-impl Root {
-    pub fn identification_division(&self) -> Option<IdentificationDivision> {
-        self.0.children().find_map(IdentificationDivision::cast)
+        self.builder.finish_node();
     }
 
-    pub fn program_id(&self) -> Option<ProgramIdClause> {
-        self.0.children().find_map(ProgramIdClause::cast)
+    fn parse_file_control(&mut self) {
+        self.builder.start_node(FILE_CONTROL_PARAGRAPH.into());
+        self.bump(); // FILE-CONTROL
+        self.expect(DOT);
+        while self.at(SELECT_KW) {
+            self.skip_ws();
+            self.parse_select_entry();
+        }
+        self.builder.finish_node();
     }
 
-    pub fn procedure_division(&self) -> Option<ProcedureDivision> {
-        self.0.children().find_map(ProcedureDivision::cast)
-    }
-}
+    fn parse_select_entry(&mut self) {
+        self.builder.start_node(SELECT_ENTRY.into());
+        self.bump(); // SELECT
+        self.eat(OPTIONAL_KW);
+        self.skip_ws();
+        self.expect(IDENT);
 
-impl ProgramIdClause {
-    pub fn name(&self) -> Option<String> {
-        self.0
-            .children_with_tokens()
-            .filter_map(|el| el.into_token())
-            .find(|t| t.kind() == IDENT)
+        loop {
+            match self.peek_significant() {
+                Some(ASSIGN_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(ASSIGN_CLAUSE.into());
+                    self.bump();
+                    self.eat(TO_KW);
+                    self.skip_ws();
+                    match self.current() {
+                        Some(IDENT) => self.bump(),
+                        Some(k) if k.is_literal_start() => self.parse_literal(),
+                        found => self.errors.push(format!("Expected assignment name, found {:?}", found)),
+                    }
+                    self.builder.finish_node();
+                }
+                Some(RELATIVE_KW) if self.nth_significant(1) == Some(KEY_KW) => {
+                    self.parse_key_clause(RELATIVE_KEY_CLAUSE);
+                }
+                Some(ORGANIZATION_KW | SEQUENTIAL_KW | INDEXED_KW | RELATIVE_KW | LINE_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(ORGANIZATION_CLAUSE.into());
+                    if self.current() == Some(ORGANIZATION_KW) {
+                        self.bump();
+                        self.eat(IS_KW);
+                    }
+                    if self.eat(LINE_KW) {
+                        self.skip_ws();
+                        self.expect(SEQUENTIAL_KW);
+                    } else if !self.eat(SEQUENTIAL_KW) && !self.eat(INDEXED_KW) && !self.eat(RELATIVE_KW) {
+                        self.errors.push("Expected file organization".to_string());
+                    }
+                    self.builder.finish_node();
+                }
+                Some(ACCESS_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(ACCESS_MODE_CLAUSE.into());
+                    self.bump();
+                    self.eat(MODE_KW);
+                    self.eat(IS_KW);
+                    if !self.eat(SEQUENTIAL_KW) && !self.eat(RANDOM_KW) && !self.eat(DYNAMIC_KW) {
+                        self.errors.push("Expected access mode".to_string());
+                    }
+                    self.builder.finish_node();
+                }
+                Some(RECORD_KW) => self.parse_key_clause(RECORD_KEY_CLAUSE),
+                Some(ALTERNATE_KW) => self.parse_key_clause(ALTERNATE_KEY_CLAUSE),
+                Some(FILE_KW | STATUS_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(FILE_STATUS_CLAUSE.into());
+                    self.eat(FILE_KW);
+                    self.eat(STATUS_KW);
+                    self.eat(IS_KW);
+                    self.skip_ws();
+                    self.expect(IDENT);
+                    self.builder.finish_node();
+                }
+                Some(DOT) => break,
+                _ if self.at_environment_boundary() || self.at(SELECT_KW) => break,
+                _ => {
+                    self.skip_ws();
+                    self.errors.push(format!("Unexpected {:?} in SELECT entry", self.current()));
+                    self.bump();
+                }
+            }
+        }
+
+        self.skip_ws();
+        self.expect(DOT);
+        self.builder.finish_node();
+    }
+
+    /// `[ALTERNATE] RECORD KEY IS name [WITH DUPLICATES]` or
+    /// `RELATIVE KEY IS name`
+    fn parse_key_clause(&mut self, kind: SyntaxKind) {
+        self.skip_ws();
+        self.builder.start_node(kind.into());
+        self.bump(); // RECORD, ALTERNATE or RELATIVE
+        self.eat(RECORD_KW);
+        self.eat(KEY_KW);
+        self.eat(IS_KW);
+        self.skip_ws();
+        self.expect(IDENT);
+        if self.eat(WITH_KW) {
+            self.skip_ws();
+            self.expect(DUPLICATES_KW);
+        } else {
+            self.eat(DUPLICATES_KW);
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_data_division(&mut self) {
+        self.builder.start_node(DATA_DIVISION.into());
+        self.bump(); // DATA
+        self.skip_ws();
+        self.expect(DIVISION_KW);
+        self.expect(DOT);
+
+        loop {
+            self.skip_ws();
+            let section = match self.current() {
+                Some(FILE_KW) => FILE_SECTION,
+                Some(WORKING_STORAGE_KW) => WORKING_STORAGE_SECTION,
+                Some(LOCAL_STORAGE_KW) => LOCAL_STORAGE_SECTION,
+                Some(LINKAGE_KW) => LINKAGE_SECTION,
+                _ => break,
+            };
+            self.parse_data_section(section);
+        }
+
+        self.builder.finish_node();
+    }
+
+    fn at_data_section_end(&self) -> bool {
+        matches!(
+            self.current(),
+            None | Some(FILE_KW | WORKING_STORAGE_KW | LOCAL_STORAGE_KW | LINKAGE_KW | PROCEDURE_KW)
+        )
+    }
+
+    fn parse_data_section(&mut self, section: SyntaxKind) {
+        self.builder.start_node(section.into());
+        self.bump(); // FILE, WORKING-STORAGE, LOCAL-STORAGE or LINKAGE
+        self.skip_ws();
+        self.expect(SECTION_KW);
+        self.expect(DOT);
+
+        loop {
+            self.skip_ws();
+            match self.current() {
+                Some(INTEGER_LITERAL) => self.parse_data_item(),
+                Some(FD_KW | SD_KW) if section == FILE_SECTION => self.parse_file_description(),
+                _ if self.at_data_section_end() => break,
+                found => {
+                    self.errors.push(format!("Expected data description entry, found {:?}", found));
+                    self.bump();
+                }
+            }
+        }
+
+        self.builder.finish_node();
+    }
+
+    fn parse_file_description(&mut self) {
+        self.builder.start_node(FILE_DESCRIPTION.into());
+        let indicator = self.current_text().unwrap_or_default().to_string();
+        self.bump(); // FD or SD
+        self.skip_ws();
+        if self.current() == Some(IDENT) {
+            self.bump(); // file name
+        } else {
+            self.errors.push(format!("Expected file name after {}", indicator));
+        }
+        self.parse_entry_clauses();
+
+        // Record descriptions
+        while self.peek_significant() == Some(INTEGER_LITERAL) {
+            self.skip_ws();
+            self.parse_data_item();
+        }
+
+        self.builder.finish_node();
+    }
+
+    /// Level number of the data description entry starting at the next
+    /// significant token, if any.
+    fn peek_level(&self) -> Option<u8> {
+        let (kind, text) = self.tokens[self.pos..].iter().find(|(k, _)| !k.is_trivia())?;
+        if *kind == INTEGER_LITERAL { text.parse().ok() } else { None }
+    }
+
+    /// Parses a data description entry together with the entries
+    /// subordinate to it.
+    fn parse_data_item(&mut self) {
+        let level = self.peek_level().unwrap_or(0);
+        self.builder.start_node(DATA_ITEM.into());
+        if !matches!(level, 1..=49 | 66 | 77 | 88) {
+            self.errors.push(format!("Invalid level number {}", self.current_text().unwrap_or_default()));
+        }
+        self.bump(); // level number
+        self.skip_ws();
+        if matches!(self.current(), Some(IDENT | FILLER_KW)) {
+            if level == 88
+                && let Some(name) = self.current_text()
+            {
+                self.condition_names.insert(name.to_ascii_uppercase());
+            }
+            self.bump(); // data name
+        }
+        self.parse_entry_clauses();
+
+        while let Some(next) = self.peek_level() {
+            if !is_subordinate(level, next) {
+                break;
+            }
+            self.skip_ws();
+            self.parse_data_item();
+        }
+
+        self.builder.finish_node();
+    }
+
+    /// Consumes the clauses of an FD or data description entry up to and
+    /// including its terminating period.
+    fn parse_entry_clauses(&mut self) {
+        loop {
+            self.skip_ws();
+            match self.current() {
+                Some(DOT) => break,
+                Some(PICTURE_KW) => self.parse_picture_clause(),
+                Some(k) if k == USAGE_KW || k.is_usage() => self.parse_usage_clause(),
+                Some(VALUE_KW) => self.parse_value_clause(),
+                Some(OCCURS_KW) => self.parse_occurs_clause(),
+                Some(REDEFINES_KW) => self.parse_redefines_clause(),
+                Some(RENAMES_KW) => self.parse_renames_clause(),
+                Some(JUSTIFIED_KW) => self.parse_justified_clause(),
+                Some(SIGN_KW | LEADING_KW | TRAILING_KW) => self.parse_sign_clause(),
+                Some(BLANK_KW) => self.parse_blank_when_zero_clause(),
+                Some(SYNCHRONIZED_KW) => self.parse_synchronized_clause(),
+                _ if self.at_data_section_end() => break,
+                _ => self.bump(),
+            }
+        }
+        self.expect(DOT);
+    }
+
+    /// `[USAGE [IS]] usage`
+    fn parse_usage_clause(&mut self) {
+        self.builder.start_node(USAGE_CLAUSE.into());
+        if self.current() == Some(USAGE_KW) {
+            self.bump();
+            self.eat(IS_KW);
+            self.skip_ws();
+        }
+        if self.current().is_some_and(SyntaxKind::is_usage) {
+            self.bump();
+        } else {
+            self.errors.push(format!("Expected usage, found {:?}", self.current()));
+        }
+        self.builder.finish_node();
+    }
+
+    /// `VALUE [IS] literal`, or for condition names
+    /// `VALUES [ARE] literal [THRU literal] ...`
+    fn parse_value_clause(&mut self) {
+        self.builder.start_node(VALUE_CLAUSE.into());
+        self.bump(); // VALUE
+        if !self.eat(IS_KW) {
+            self.eat(ARE_KW);
+        }
+        let mut found = false;
+        while self.peek_significant().is_some_and(SyntaxKind::is_literal_start) {
+            self.skip_ws();
+            let checkpoint = self.builder.checkpoint();
+            self.parse_literal();
+            if self.eat(THRU_KW) {
+                self.builder.start_node_at(checkpoint, VALUE_RANGE.into());
+                self.skip_ws();
+                if self.current().is_some_and(SyntaxKind::is_literal_start) {
+                    self.parse_literal();
+                } else {
+                    self.errors.push("Expected literal after THRU".to_string());
+                }
+                self.builder.finish_node();
+            }
+            found = true;
+        }
+        if !found {
+            self.errors.push("Expected literal after VALUE".to_string());
+        }
+        self.builder.finish_node();
+    }
+
+    /// `OCCURS [min TO] max [TIMES] [DEPENDING ON name]
+    /// [ASCENDING|DESCENDING KEY IS name...] [INDEXED BY index...]`
+    fn parse_occurs_clause(&mut self) {
+        self.builder.start_node(OCCURS_CLAUSE.into());
+        self.bump(); // OCCURS
+        self.skip_ws();
+        self.expect(INTEGER_LITERAL);
+        if self.eat(TO_KW) {
+            self.skip_ws();
+            self.expect(INTEGER_LITERAL);
+        }
+        self.eat(TIMES_KW);
+
+        loop {
+            match self.peek_significant() {
+                Some(DEPENDING_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(OCCURS_DEPENDING.into());
+                    self.bump();
+                    self.eat(ON_KW);
+                    self.parse_data_ref();
+                    self.builder.finish_node();
+                }
+                Some(ASCENDING_KW | DESCENDING_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(OCCURS_KEY.into());
+                    self.bump();
+                    self.eat(KEY_KW);
+                    self.eat(IS_KW);
+                    while self.at(IDENT) {
+                        self.parse_data_ref();
+                    }
+                    self.builder.finish_node();
+                }
+                Some(INDEXED_KW) => {
+                    self.skip_ws();
+                    self.builder.start_node(INDEXED_BY.into());
+                    self.bump();
+                    self.eat(BY_KW);
+                    while self.eat(IDENT) {}
+                    self.builder.finish_node();
+                }
+                _ => break,
+            }
+        }
+
+        self.builder.finish_node();
+    }
+
+    fn parse_redefines_clause(&mut self) {
+        self.builder.start_node(REDEFINES_CLAUSE.into());
+        self.bump(); // REDEFINES
+        self.skip_ws();
+        self.expect(IDENT);
+        self.builder.finish_node();
+    }
+
+    /// `RENAMES name [THRU name]` of a level 66 entry
+    fn parse_renames_clause(&mut self) {
+        self.builder.start_node(RENAMES_CLAUSE.into());
+        self.bump(); // RENAMES
+        self.skip_ws();
+        self.expect(IDENT);
+        if self.eat(THRU_KW) {
+            self.skip_ws();
+            self.expect(IDENT);
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_justified_clause(&mut self) {
+        self.builder.start_node(JUSTIFIED_CLAUSE.into());
+        self.bump(); // JUSTIFIED
+        self.eat(RIGHT_KW);
+        self.builder.finish_node();
+    }
+
+    /// `[SIGN [IS]] LEADING|TRAILING [SEPARATE [CHARACTER]]`
+    fn parse_sign_clause(&mut self) {
+        self.builder.start_node(SIGN_CLAUSE.into());
+        if self.current() == Some(SIGN_KW) {
+            self.bump();
+            self.eat(IS_KW);
+        }
+        if !self.eat(LEADING_KW) && !self.eat(TRAILING_KW) {
+            self.errors.push("Expected LEADING or TRAILING in SIGN clause".to_string());
+        }
+        if self.eat(SEPARATE_KW) {
+            self.eat(CHARACTER_KW);
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_blank_when_zero_clause(&mut self) {
+        self.builder.start_node(BLANK_WHEN_ZERO_CLAUSE.into());
+        self.bump(); // BLANK
+        self.eat(WHEN_KW);
+        if !self.eat(ZERO_KW) {
+            self.errors.push("Expected ZERO after BLANK WHEN".to_string());
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_synchronized_clause(&mut self) {
+        self.builder.start_node(SYNCHRONIZED_CLAUSE.into());
+        self.bump(); // SYNCHRONIZED
+        if !self.eat(LEFT_KW) {
+            self.eat(RIGHT_KW);
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_picture_clause(&mut self) {
+        self.builder.start_node(PICTURE_CLAUSE.into());
+        self.bump(); // PIC or PICTURE
+        self.skip_ws();
+        if self.current() == Some(IS_KW) {
+            self.bump();
+            self.skip_ws();
+        }
+        if self.current() == Some(PICTURE_STRING) {
+            let text = self.current_text().unwrap_or_default().to_string();
+            if let Err(err) = Picture::parse_with_decimal_point(&text, self.decimal_point_is_comma) {
+                self.errors.push(format!("Invalid PICTURE string {}: {}", text, err));
+            }
+            self.bump();
+        } else {
+            self.errors.push("Expected PICTURE character-string".to_string());
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_procedure_division(&mut self) {
+        self.builder.start_node(PROCEDURE_DIVISION.into());
+        self.bump(); // PROCEDURE
+        self.skip_ws();
+        self.expect(DIVISION_KW);
+        if self.at(USING_KW) {
+            self.parse_using_phrase();
+        }
+        if self.at(RETURNING_KW) {
+            self.parse_returning_phrase();
+        }
+        self.skip_ws();
+        self.expect(DOT);
+
+        // Sentences may precede the first paragraph
+        self.parse_sentences();
+        while self.current().is_some() {
+            if self.at_section_header() {
+                self.parse_section();
+            } else {
+                self.parse_paragraph();
+            }
+        }
+
+        self.builder.finish_node();
+    }
+
+    /// `name SECTION.`, where the name may be all digits as in `0100 SECTION.`
+    fn at_section_header(&self) -> bool {
+        matches!(self.peek_significant(), Some(IDENT | INTEGER_LITERAL)) && self.nth_significant(1) == Some(SECTION_KW)
+    }
+
+    /// `name.` at the start of a sentence, where the name may be all digits.
+    fn at_paragraph_header(&self) -> bool {
+        matches!(self.peek_significant(), Some(IDENT | INTEGER_LITERAL)) && self.nth_significant(1) == Some(DOT)
+    }
+
+    fn parse_section(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(SECTION.into());
+        self.bump(); // section name
+        self.skip_ws();
+        self.bump(); // SECTION
+        self.expect(DOT);
+        self.parse_sentences();
+        while self.at_paragraph_header() {
+            self.parse_paragraph();
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_paragraph(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(PARAGRAPH.into());
+        if matches!(self.current(), Some(IDENT | INTEGER_LITERAL)) {
+            self.bump(); // paragraph name
+            self.expect(DOT);
+        } else {
+            self.errors.push(format!("Expected paragraph name, found {:?}", self.current()));
+        }
+        self.parse_sentences();
+        self.builder.finish_node();
+    }
+
+    /// Sentences up to the next section or paragraph header.
+    fn parse_sentences(&mut self) {
+        while self.peek_significant().is_some() && !self.at_section_header() && !self.at_paragraph_header() {
+            self.parse_sentence();
+        }
+        self.skip_ws();
+    }
+
+    /// Statements ended by a period.
+    fn parse_sentence(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(SENTENCE.into());
+        loop {
+            self.skip_ws();
+            match self.current() {
+                None | Some(DOT) => break,
+                _ if self.at_section_header() => break,
+                _ => self.parse_statement(),
+            }
+        }
+        if !self.eat(DOT) && self.current().is_some() {
+            self.errors.push("Expected . at end of sentence".to_string());
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_statement(&mut self) {
+        match self.current() {
+            Some(DISPLAY_KW) => self.parse_display_stmt(),
+            Some(MOVE_KW) => self.parse_move_stmt(),
+            Some(ADD_KW) => self.parse_arithmetic_stmt(ADD_STMT, END_ADD_KW),
+            Some(SUBTRACT_KW) => self.parse_arithmetic_stmt(SUBTRACT_STMT, END_SUBTRACT_KW),
+            Some(MULTIPLY_KW) => self.parse_arithmetic_stmt(MULTIPLY_STMT, END_MULTIPLY_KW),
+            Some(DIVIDE_KW) => self.parse_arithmetic_stmt(DIVIDE_STMT, END_DIVIDE_KW),
+            Some(COMPUTE_KW) => self.parse_compute_stmt(),
+            Some(IF_KW) => self.parse_if_stmt(),
+            Some(EVALUATE_KW) => self.parse_evaluate_stmt(),
+            Some(PERFORM_KW) => self.parse_perform_stmt(),
+            Some(GO_KW) => self.parse_go_to_stmt(),
+            Some(EXIT_KW) => self.parse_exit_stmt(),
+            Some(STOP_KW) => self.parse_stop_stmt(),
+            Some(GOBACK_KW) => self.parse_keyword_stmt(GOBACK_STMT),
+            Some(CONTINUE_KW) => self.parse_keyword_stmt(CONTINUE_STMT),
+            Some(NEXT_KW) => self.parse_next_sentence(),
+            Some(ALTER_KW) => self.parse_alter_stmt(),
+            Some(STRING_KW) => self.parse_string_stmt(),
+            Some(UNSTRING_KW) => self.parse_unstring_stmt(),
+            Some(INSPECT_KW) => self.parse_inspect_stmt(),
+            Some(OPEN_KW) => self.parse_open_stmt(),
+            Some(CLOSE_KW) => self.parse_close_stmt(),
+            Some(READ_KW) => self.parse_read_stmt(),
+            Some(WRITE_KW) => self.parse_write_stmt(),
+            Some(REWRITE_KW) => self.parse_rewrite_stmt(),
+            Some(DELETE_KW) => self.parse_delete_stmt(),
+            Some(START_KW) => self.parse_start_stmt(),
+            Some(CALL_KW) => self.parse_call_stmt(),
+            Some(CANCEL_KW) => self.parse_cancel_stmt(),
+            Some(ACCEPT_KW) => self.parse_accept_stmt(),
+            Some(SEARCH_KW) => self.parse_search_stmt(),
+            Some(SET_KW) => self.parse_set_stmt(),
+            Some(SORT_KW) => self.parse_sort_stmt(SORT_STMT),
+            Some(MERGE_KW) => self.parse_sort_stmt(MERGE_STMT),
+            Some(RELEASE_KW) => self.parse_release_stmt(),
+            Some(RETURN_KW) => self.parse_return_stmt(),
+            found => {
+                self.errors.push(format!("Unexpected {:?} in sentence", found));
+                self.bump();
+            }
+        }
+    }
+
+    /// A literal, a data reference or an intrinsic function call.
+    fn parse_operand(&mut self) {
+        self.skip_ws();
+        match self.current() {
+            Some(k) if k.is_literal_start() => self.parse_literal(),
+            Some(IDENT) => self.parse_data_ref(),
+            Some(FUNCTION_KW) => self.parse_function_call(),
+            found => self.errors.push(format!("Expected literal or identifier, found {:?}", found)),
+        }
+    }
+
+    /// `name [{OF | IN} qualifier]... [(subscript ...)] [(start:[length])]`,
+    /// where a subscript is ALL or an arithmetic expression such as the
+    /// relative `index + 1`, and so are the start and length.
+    fn parse_data_ref(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(DATA_REF.into());
+        self.expect(IDENT);
+        while (self.at(OF_KW) || self.at(IN_KW)) && self.nth_significant(1) == Some(IDENT) {
+            self.skip_ws();
+            self.bump(); // OF or IN
+            self.skip_ws();
+            self.bump(); // qualifier
+        }
+        if self.at(L_PAREN) && !self.at_ref_mod() {
+            self.skip_ws();
+            self.builder.start_node(SUBSCRIPTS.into());
+            self.bump(); // (
+            while !self.at(R_PAREN) && self.at_expr() {
+                if !self.eat(ALL_KW) {
+                    self.parse_expr();
+                }
+            }
+            self.skip_ws();
+            self.expect(R_PAREN);
+            self.builder.finish_node();
+        }
+        if self.at(L_PAREN) && self.at_ref_mod() {
+            self.parse_ref_mod();
+        }
+        self.builder.finish_node();
+    }
+
+    /// `(start:[length])`
+    fn parse_ref_mod(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(REF_MOD.into());
+        self.bump(); // (
+        self.parse_expr();
+        self.skip_ws();
+        self.expect(COLON);
+        if !self.at(R_PAREN) {
+            self.parse_expr();
+        }
+        self.skip_ws();
+        self.expect(R_PAREN);
+        self.builder.finish_node();
+    }
+
+    /// `FUNCTION name [(argument...)] [(start:[length])]`. The name and the
+    /// number of arguments are checked against the intrinsic catalogue;
+    /// unknown names are only warned about, as vendors add their own.
+    fn parse_function_call(&mut self) {
+        self.builder.start_node(FUNCTION_CALL.into());
+        self.bump(); // FUNCTION
+        self.skip_ws();
+        // Some names, such as RANDOM and SIGN, are also keywords
+        let name = self
+            .current_text()
+            .filter(|t| t.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .map(str::to_string);
+        let Some(name) = name else {
+            self.errors.push(format!("Expected function name, found {:?}", self.current()));
+            self.builder.finish_node();
+            return;
+        };
+        self.bump();
+
+        let mut arguments = 0;
+        if self.at(L_PAREN) && !self.at_ref_mod() {
+            self.skip_ws();
+            self.builder.start_node(FUNCTION_ARGS.into());
+            self.bump(); // (
+            while !self.at(R_PAREN) && (self.at_expr() || self.at(LEADING_KW) || self.at(TRAILING_KW)) {
+                if !self.eat(LEADING_KW) && !self.eat(TRAILING_KW) {
+                    self.parse_expr();
+                }
+                arguments += 1;
+            }
+            self.skip_ws();
+            self.expect(R_PAREN);
+            self.builder.finish_node();
+        }
+        if self.at(L_PAREN) && self.at_ref_mod() {
+            self.parse_ref_mod();
+        }
+        self.builder.finish_node();
+
+        match intrinsics::lookup(&name) {
+            Some(function) if !function.accepts(arguments) => self.errors.push(format!(
+                "FUNCTION {} takes {}, found {}",
+                function.name,
+                function.arity(),
+                arguments
+            )),
+            Some(_) => {}
+            None => self.warnings.push(format!("Unknown intrinsic function {}", name)),
+        }
+    }
+
+    /// `MOVE [CORRESPONDING] source TO target ...`
+    fn parse_move_stmt(&mut self) {
+        self.builder.start_node(MOVE_STMT.into());
+        self.bump(); // MOVE
+        self.eat(CORRESPONDING_KW);
+        self.parse_operand();
+        self.skip_ws();
+        self.expect(TO_KW);
+        if !self.at(IDENT) {
+            self.errors.push("Expected receiving item after TO".to_string());
+        }
+        while self.at(IDENT) {
+            self.parse_data_ref();
+        }
+        self.builder.finish_node();
+    }
+
+    /// Statements up to the next phrase, scope terminator or period.
+    fn parse_imperative_statements(&mut self) {
+        while self.peek_significant().is_some_and(SyntaxKind::is_verb) {
+            self.skip_ws();
+            self.parse_statement();
+        }
+    }
+
+    fn at_operand(&self) -> bool {
+        self.peek_significant().is_some_and(|k| k == IDENT || k == FUNCTION_KW || k.is_literal_start())
+    }
+
+    fn at_expr(&self) -> bool {
+        self.at_operand() || matches!(self.peek_significant(), Some(L_PAREN | PLUS | MINUS))
+    }
+
+    /// `name [ROUNDED [MODE IS mode]]`
+    fn parse_receiver(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(RECEIVER.into());
+        self.parse_data_ref();
+        if self.eat(ROUNDED_KW) && self.eat(MODE_KW) {
+            self.eat(IS_KW);
+            self.skip_ws();
+            self.expect(IDENT);
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_receivers(&mut self) {
+        if !self.at(IDENT) {
+            self.errors.push(format!("Expected receiving item, found {:?}", self.peek_significant()));
+        }
+        while self.at(IDENT) {
+            self.parse_receiver();
+        }
+    }
+
+    /// ADD, SUBTRACT, MULTIPLY and DIVIDE. The items after TO, FROM, BY or
+    /// INTO are receivers, or operands when a GIVING phrase follows.
+    fn parse_arithmetic_stmt(&mut self, kind: SyntaxKind, end: SyntaxKind) {
+        self.builder.start_node(kind.into());
+        self.bump(); // verb
+        self.eat(CORRESPONDING_KW);
+        if !self.at_operand() {
+            self.errors.push(format!("Expected operand, found {:?}", self.peek_significant()));
+        }
+        while self.at_operand() {
+            self.parse_operand();
+        }
+
+        if self.eat(TO_KW) || self.eat(FROM_KW) || self.eat(BY_KW) || self.eat(INTO_KW) {
+            while self.at_operand() {
+                if self.at(IDENT) {
+                    self.parse_receiver();
+                } else {
+                    self.parse_operand();
+                }
+            }
+        }
+        if self.at(GIVING_KW) {
+            self.skip_ws();
+            self.builder.start_node(GIVING_PHRASE.into());
+            self.bump();
+            self.parse_receivers();
+            self.builder.finish_node();
+        }
+        if kind == DIVIDE_STMT && self.at(REMAINDER_KW) {
+            self.skip_ws();
+            self.builder.start_node(REMAINDER_PHRASE.into());
+            self.bump();
+            self.parse_receivers();
+            self.builder.finish_node();
+        }
+
+        self.parse_size_error_phrases();
+        self.eat(end);
+        self.builder.finish_node();
+    }
+
+    /// `COMPUTE receiver ... = expression`
+    fn parse_compute_stmt(&mut self) {
+        self.builder.start_node(COMPUTE_STMT.into());
+        self.bump(); // COMPUTE
+        self.parse_receivers();
+        if !self.eat(EQUALS) && !self.eat(EQUAL_KW) {
+            self.errors.push(format!("Expected = in COMPUTE, found {:?}", self.peek_significant()));
+        }
+        self.parse_expr();
+        self.parse_size_error_phrases();
+        self.eat(END_COMPUTE_KW);
+        self.builder.finish_node();
+    }
+
+    /// `[ON] SIZE ERROR imperative` and `NOT [ON] SIZE ERROR imperative`
+    fn parse_size_error_phrases(&mut self) {
+        self.parse_handler_phrases([ON_SIZE_ERROR, NOT_ON_SIZE_ERROR], ON_KW, SIZE_KW, Some((ERROR_KW, true)));
+    }
+
+    /// `[ON] OVERFLOW imperative` and `NOT [ON] OVERFLOW imperative`
+    fn parse_overflow_phrases(&mut self) {
+        self.parse_handler_phrases([ON_OVERFLOW, NOT_ON_OVERFLOW], ON_KW, OVERFLOW_KW, None);
+    }
+
+    /// `[AT] END imperative` and `NOT [AT] END imperative`
+    fn parse_at_end_phrases(&mut self) {
+        self.parse_handler_phrases([AT_END, NOT_AT_END], AT_KW, END_KW, None);
+    }
+
+    /// `INVALID [KEY] imperative` and `NOT INVALID [KEY] imperative`
+    fn parse_invalid_key_phrases(&mut self) {
+        self.parse_handler_phrases([INVALID_KEY, NOT_INVALID_KEY], INVALID_KW, INVALID_KW, Some((KEY_KW, false)));
+    }
+
+    /// A phrase `[prefix] keyword [suffix] imperative` and its NOT
+    /// counterpart, as in `[ON] SIZE ERROR` or `[AT] END`. The suffix comes
+    /// with whether it is required.
+    fn parse_handler_phrases(
+        &mut self,
+        kinds: [SyntaxKind; 2],
+        prefix: SyntaxKind,
+        keyword: SyntaxKind,
+        suffix: Option<(SyntaxKind, bool)>,
+    ) {
+        for (negated, kind) in [false, true].into_iter().zip(kinds) {
+            let offset = usize::from(negated);
+            let at_phrase = (!negated || self.at(NOT_KW))
+                && match self.nth_significant(offset) {
+                    Some(k) if k == prefix && self.nth_significant(offset + 1) == Some(keyword) => true,
+                    Some(k) => k == keyword,
+                    None => false,
+                };
+            if !at_phrase {
+                continue;
+            }
+            self.skip_ws();
+            self.builder.start_node(kind.into());
+            self.eat(NOT_KW);
+            self.eat(prefix);
+            self.eat(keyword);
+            match suffix {
+                Some((suffix, true)) => {
+                    self.skip_ws();
+                    self.expect(suffix);
+                }
+                Some((suffix, false)) => {
+                    self.eat(suffix);
+                }
+                None => {}
+            }
+            self.parse_imperative_statements();
+            self.builder.finish_node();
+        }
+    }
+
+    /// Arithmetic expression. Unary signs bind tightest, then `**`, then
+    /// `*` and `/`, then `+` and `-`; each level associates to the left.
+    fn parse_expr(&mut self) {
+        self.parse_binary_expr(0);
+    }
+
+    fn parse_binary_expr(&mut self, min_level: u8) {
+        self.skip_ws();
+        let checkpoint = self.builder.checkpoint();
+        self.parse_prefix_expr();
+        loop {
+            let level = match self.peek_significant() {
+                Some(PLUS | MINUS) => 1,
+                Some(STAR | SLASH) => 2,
+                Some(POWER) => 3,
+                _ => break,
+            };
+            if level <= min_level {
+                break;
+            }
+            self.builder.start_node_at(checkpoint, BIN_EXPR.into());
+            self.skip_ws();
+            self.bump(); // operator
+            self.parse_binary_expr(level);
+            self.builder.finish_node();
+        }
+    }
+
+    fn parse_prefix_expr(&mut self) {
+        self.skip_ws();
+        match self.current() {
+            Some(PLUS | MINUS) => {
+                self.builder.start_node(PREFIX_EXPR.into());
+                self.bump();
+                self.parse_prefix_expr();
+                self.builder.finish_node();
+            }
+            Some(L_PAREN) => {
+                self.builder.start_node(PAREN_EXPR.into());
+                self.bump();
+                self.parse_expr();
+                self.skip_ws();
+                self.expect(R_PAREN);
+                self.builder.finish_node();
+            }
+            _ => self.parse_operand(),
+        }
+    }
+
+    /// `IF condition [THEN] statements [ELSE statements] [END-IF]`
+    fn parse_if_stmt(&mut self) {
+        self.builder.start_node(IF_STMT.into());
+        self.bump(); // IF
+        self.parse_condition();
+
+        self.skip_ws();
+        self.builder.start_node(THEN_BRANCH.into());
+        self.eat(THEN_KW);
+        self.parse_branch_statements("IF condition");
+        self.builder.finish_node();
+
+        if self.at(ELSE_KW) {
+            self.skip_ws();
+            self.builder.start_node(ELSE_BRANCH.into());
+            self.bump();
+            self.parse_branch_statements("ELSE");
+            self.builder.finish_node();
+        }
+        self.eat(END_IF_KW);
+        self.builder.finish_node();
+    }
+
+    fn parse_branch_statements(&mut self, after: &str) {
+        if !self.peek_significant().is_some_and(SyntaxKind::is_verb) {
+            self.errors.push(format!("Expected statement after {}, found {:?}", after, self.peek_significant()));
+        }
+        self.parse_imperative_statements();
+    }
+
+    fn parse_condition(&mut self) {
+        let outer = std::mem::replace(&mut self.relation_seen, false);
+        self.parse_logical_condition(OR_KW);
+        self.relation_seen = outer;
+    }
+
+    /// Conditions joined by `op`; AND binds tighter than OR.
+    fn parse_logical_condition(&mut self, op: SyntaxKind) {
+        self.skip_ws();
+        let checkpoint = self.builder.checkpoint();
+        self.parse_logical_operand(op);
+        while self.at(op) {
+            self.builder.start_node_at(checkpoint, COMBINED_CONDITION.into());
+            self.eat(op);
+            self.parse_logical_operand(op);
+            self.builder.finish_node();
+        }
+    }
+
+    fn parse_logical_operand(&mut self, op: SyntaxKind) {
+        if op == OR_KW {
+            self.parse_logical_condition(AND_KW);
+        } else {
+            self.parse_not_condition();
+        }
+    }
+
+    fn parse_not_condition(&mut self) {
+        self.skip_ws();
+        // In `A = 1 OR NOT > 2` the NOT belongs to the relational operator
+        if self.at(NOT_KW) && !(self.relation_seen && self.rel_op_at(0)) {
+            self.builder.start_node(NOT_CONDITION.into());
+            self.bump();
+            self.parse_not_condition();
+            self.builder.finish_node();
+        } else {
+            self.parse_simple_condition();
+        }
+    }
+
+    fn parse_simple_condition(&mut self) {
+        self.skip_ws();
+        if self.current() == Some(L_PAREN) && !self.paren_starts_expr() {
+            self.builder.start_node(PAREN_CONDITION.into());
+            self.bump();
+            self.parse_logical_condition(OR_KW);
+            self.skip_ws();
+            self.expect(R_PAREN);
+            self.builder.finish_node();
+            return;
+        }
+        if self.relation_seen && self.rel_op_at(0) {
+            // Abbreviated relation with the subject omitted: `... OR > B`
+            self.builder.start_node(RELATION_CONDITION.into());
+            self.parse_rel_op();
+            self.parse_expr();
+            self.builder.finish_node();
+            return;
+        }
+
+        // After a relation, `AND WS-EOF` is a condition name when WS-EOF is
+        // declared at level 88, and an abbreviated relation otherwise
+        let condition_name = self.peek_significant() == Some(IDENT)
+            && self.tokens[self.pos..]
+                .iter()
+                .find(|(k, _)| !k.is_trivia())
+                .is_some_and(|(_, text)| self.condition_names.contains(&text.to_ascii_uppercase()));
+        let checkpoint = self.builder.checkpoint();
+        self.parse_expr();
+        if self.rel_op_at(0) {
+            self.builder.start_node_at(checkpoint, RELATION_CONDITION.into());
+            self.parse_rel_op();
+            self.parse_expr();
+            self.builder.finish_node();
+            self.relation_seen = true;
+        } else if let Some(kind) = self.class_or_sign_at(0) {
+            self.builder.start_node_at(checkpoint, kind.into());
+            self.eat(IS_KW);
+            self.eat(NOT_KW);
+            self.skip_ws();
+            self.bump(); // class or sign
+            self.builder.finish_node();
+        } else if self.relation_seen && !condition_name {
+            // Abbreviated relation with subject and operator omitted: `... OR 2`
+            self.builder.start_node_at(checkpoint, RELATION_CONDITION.into());
+            self.builder.finish_node();
+        } else {
+            self.builder.start_node_at(checkpoint, CONDITION_NAME.into());
+            self.builder.finish_node();
+        }
+    }
+
+    /// Whether a relational operator starts at the `n`th significant token.
+    fn rel_op_at(&self, mut n: usize) -> bool {
+        if self.nth_significant(n) == Some(IS_KW) {
+            n += 1;
+        }
+        if self.nth_significant(n) == Some(NOT_KW) {
+            n += 1;
+        }
+        matches!(
+            self.nth_significant(n),
+            Some(GREATER_KW | LESS_KW | EQUAL_KW | EQUALS | GREATER | LESS | GREATER_EQUALS | LESS_EQUALS)
+        )
+    }
+
+    /// CLASS_CONDITION or SIGN_CONDITION if one of their keywords starts at
+    /// the `n`th significant token.
+    fn class_or_sign_at(&self, mut n: usize) -> Option<SyntaxKind> {
+        if self.nth_significant(n) == Some(IS_KW) {
+            n += 1;
+        }
+        if self.nth_significant(n) == Some(NOT_KW) {
+            n += 1;
+        }
+        match self.nth_significant(n)? {
+            NUMERIC_KW | ALPHABETIC_KW | ALPHABETIC_LOWER_KW | ALPHABETIC_UPPER_KW => Some(CLASS_CONDITION),
+            POSITIVE_KW | NEGATIVE_KW | ZERO_KW => Some(SIGN_CONDITION),
+            _ => None,
+        }
+    }
+
+    /// Whether the parenthesis at the next significant token opens an
+    /// arithmetic expression, as in `(A + B) > C`, rather than a condition.
+    fn paren_starts_expr(&self) -> bool {
+        let mut depth = 0;
+        let mut n = 0;
+        while let Some(kind) = self.nth_significant(n) {
+            match kind {
+                L_PAREN => depth += 1,
+                R_PAREN => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                DOT => return false,
+                _ => {}
+            }
+            n += 1;
+        }
+        matches!(self.nth_significant(n + 1), Some(PLUS | MINUS | STAR | SLASH | POWER))
+            || self.rel_op_at(n + 1)
+            || self.class_or_sign_at(n + 1).is_some()
+    }
+
+    fn parse_rel_op(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(REL_OP.into());
+        self.eat(IS_KW);
+        self.eat(NOT_KW);
+        self.skip_ws();
+        match self.current() {
+            Some(GREATER_KW | LESS_KW) => {
+                self.bump();
+                self.eat(THAN_KW);
+                if self.at(OR_KW) && self.nth_significant(1) == Some(EQUAL_KW) {
+                    self.eat(OR_KW);
+                    self.eat(EQUAL_KW);
+                    self.eat(TO_KW);
+                }
+            }
+            Some(EQUAL_KW) => {
+                self.bump();
+                self.eat(TO_KW);
+            }
+            _ => self.bump(),
+        }
+        self.builder.finish_node();
+    }
+
+    /// `EVALUATE subject [ALSO subject]... {WHEN object [ALSO object]...
+    /// statements}... [WHEN OTHER statements] [END-EVALUATE]`
+    fn parse_evaluate_stmt(&mut self) {
+        self.builder.start_node(EVALUATE_STMT.into());
+        self.bump(); // EVALUATE
+        loop {
+            self.skip_ws();
+            self.builder.start_node(EVALUATE_SUBJECT.into());
+            match self.current() {
+                Some(TRUE_KW | FALSE_KW) => self.bump(),
+                _ if self.condition_ahead(0) => self.parse_condition(),
+                _ => self.parse_expr(),
+            }
+            self.builder.finish_node();
+            if !self.eat(ALSO_KW) {
+                break;
+            }
+        }
+
+        if !self.at(WHEN_KW) {
+            self.errors.push(format!("Expected WHEN, found {:?}", self.peek_significant()));
+        }
+        while self.at(WHEN_KW) {
+            self.skip_ws();
+            if self.nth_significant(1) == Some(OTHER_KW) {
+                self.builder.start_node(WHEN_OTHER.into());
+                self.bump(); // WHEN
+                self.eat(OTHER_KW);
+                self.parse_branch_statements("WHEN OTHER");
+                self.builder.finish_node();
+                break;
+            }
+            self.builder.start_node(WHEN_BRANCH.into());
+            while self.at(WHEN_KW) && self.nth_significant(1) != Some(OTHER_KW) {
+                self.skip_ws();
+                self.builder.start_node(WHEN_PHRASE.into());
+                self.bump(); // WHEN
+                loop {
+                    self.parse_when_object();
+                    if !self.eat(ALSO_KW) {
+                        break;
+                    }
+                }
+                self.builder.finish_node();
+            }
+            self.parse_branch_statements("WHEN");
+            self.builder.finish_node();
+        }
+
+        self.eat(END_EVALUATE_KW);
+        self.builder.finish_node();
+    }
+
+    /// Out-of-line `PERFORM procedure [THRU procedure] [loop]` or inline
+    /// `PERFORM [loop] statements END-PERFORM`, where the loop is `n TIMES`,
+    /// `[WITH TEST BEFORE|AFTER] UNTIL condition` or
+    /// `[WITH TEST BEFORE|AFTER] VARYING ... [AFTER ...]...`.
+    fn parse_perform_stmt(&mut self) {
+        self.builder.start_node(PERFORM_STMT.into());
+        self.bump(); // PERFORM
+
+        let out_of_line = self.at(IDENT) && self.nth_significant(1) != Some(TIMES_KW);
+        if out_of_line {
+            self.parse_procedure_range();
+        }
+
+        if self.at_operand() && self.nth_significant(1) == Some(TIMES_KW) {
+            self.skip_ws();
+            self.builder.start_node(TIMES_PHRASE.into());
+            self.parse_operand();
+            self.eat(TIMES_KW);
+            self.builder.finish_node();
+        } else {
+            if self.at(WITH_KW) || self.at(TEST_KW) {
+                self.skip_ws();
+                self.builder.start_node(TEST_PHRASE.into());
+                self.eat(WITH_KW);
+                self.skip_ws();
+                self.expect(TEST_KW);
+                if !self.eat(BEFORE_KW) && !self.eat(AFTER_KW) {
+                    self.errors.push("Expected BEFORE or AFTER after TEST".to_string());
+                }
+                self.builder.finish_node();
+            }
+            if self.at(UNTIL_KW) {
+                self.skip_ws();
+                self.builder.start_node(UNTIL_PHRASE.into());
+                self.bump();
+                self.parse_condition();
+                self.builder.finish_node();
+            } else if self.at(VARYING_KW) {
+                self.parse_varying_phrase();
+                // AFTER phrases only nest loops of an out-of-line PERFORM
+                while out_of_line && self.at(AFTER_KW) {
+                    self.parse_varying_phrase();
+                }
+            }
+        }
+
+        if !out_of_line {
+            self.parse_imperative_statements();
+            self.skip_ws();
+            self.expect(END_PERFORM_KW);
+        }
+        self.builder.finish_node();
+    }
+
+    /// `procedure-name [THRU procedure-name]`
+    fn parse_procedure_range(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(PROCEDURE_RANGE.into());
+        self.expect(IDENT);
+        if self.eat(THRU_KW) {
+            self.skip_ws();
+            self.expect(IDENT);
+        }
+        self.builder.finish_node();
+    }
+
+    /// `VARYING|AFTER identifier FROM operand BY operand UNTIL condition`
+    fn parse_varying_phrase(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(VARYING_PHRASE.into());
+        self.bump(); // VARYING or AFTER
+        self.parse_data_ref();
+        self.skip_ws();
+        self.expect(FROM_KW);
+        self.parse_operand();
+        self.skip_ws();
+        self.expect(BY_KW);
+        self.parse_operand();
+        self.skip_ws();
+        self.expect(UNTIL_KW);
+        self.parse_condition();
+        self.builder.finish_node();
+    }
+
+    /// A statement that is a single keyword, e.g. GOBACK.
+    fn parse_keyword_stmt(&mut self, kind: SyntaxKind) {
+        self.builder.start_node(kind.into());
+        self.bump();
+        self.builder.finish_node();
+    }
+
+    /// `GO [TO] procedure` or `GO [TO] procedure... DEPENDING [ON] identifier`.
+    /// The procedure may be omitted when an ALTER supplies it.
+    fn parse_go_to_stmt(&mut self) {
+        self.builder.start_node(GO_TO_STMT.into());
+        self.bump(); // GO
+        self.eat(TO_KW);
+        while self.eat(IDENT) {}
+        if self.eat(DEPENDING_KW) {
+            self.eat(ON_KW);
+            self.parse_data_ref();
+        }
+        self.builder.finish_node();
+    }
+
+    /// `EXIT [PROGRAM | PARAGRAPH | SECTION | PERFORM [CYCLE]]`
+    fn parse_exit_stmt(&mut self) {
+        self.builder.start_node(EXIT_STMT.into());
+        self.bump(); // EXIT
+        if !self.eat(PROGRAM_KW) && !self.eat(PARAGRAPH_KW) && !self.eat(SECTION_KW) && self.eat(PERFORM_KW) {
+            self.eat(CYCLE_KW);
+        }
+        self.builder.finish_node();
+    }
+
+    /// `STOP RUN` or `STOP literal`
+    fn parse_stop_stmt(&mut self) {
+        self.builder.start_node(STOP_STMT.into());
+        self.bump(); // STOP
+        self.skip_ws();
+        match self.current() {
+            Some(RUN_KW) => self.bump(),
+            Some(k) if k.is_literal_start() => self.parse_literal(),
+            found => self.errors.push(format!("Expected RUN or literal after STOP, found {:?}", found)),
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_next_sentence(&mut self) {
+        self.builder.start_node(NEXT_SENTENCE_STMT.into());
+        self.bump(); // NEXT
+        self.skip_ws();
+        self.expect(SENTENCE_KW);
+        self.builder.finish_node();
+    }
+
+    /// `ALTER procedure TO [PROCEED TO] procedure ...`
+    fn parse_alter_stmt(&mut self) {
+        self.builder.start_node(ALTER_STMT.into());
+        self.bump(); // ALTER
+        while self.at(IDENT) {
+            self.skip_ws();
+            let altered = self.current_text().unwrap_or_default().to_string();
+            self.bump();
+            self.skip_ws();
+            self.expect(TO_KW);
+            if self.eat(PROCEED_KW) {
+                self.skip_ws();
+                self.expect(TO_KW);
+            }
+            self.skip_ws();
+            self.expect(IDENT);
+            self.warnings.push(format!("ALTER changes the GO TO in {} at run time", altered));
+        }
+        self.builder.finish_node();
+    }
+
+    /// `STRING {value... DELIMITED [BY] {value | SIZE}}... INTO identifier
+    /// [WITH POINTER identifier] [ON OVERFLOW ...] [NOT ON OVERFLOW ...]`
+    fn parse_string_stmt(&mut self) {
+        self.builder.start_node(STRING_STMT.into());
+        self.bump(); // STRING
+        while self.at_operand() {
+            self.skip_ws();
+            self.builder.start_node(STRING_SOURCE.into());
+            while self.at_operand() {
+                self.parse_operand();
+            }
+            self.skip_ws();
+            self.expect(DELIMITED_KW);
+            self.eat(BY_KW);
+            if !self.eat(SIZE_KW) {
+                self.parse_operand();
+            }
+            self.builder.finish_node();
+        }
+        self.parse_into_phrase();
+        self.parse_pointer_phrase();
+        self.parse_overflow_phrases();
+        self.eat(END_STRING_KW);
+        self.builder.finish_node();
+    }
+
+    fn parse_into_phrase(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(INTO_PHRASE.into());
+        self.expect(INTO_KW);
+        self.parse_data_ref();
+        self.builder.finish_node();
+    }
+
+    /// `[WITH] POINTER identifier`
+    fn parse_pointer_phrase(&mut self) {
+        if self.at(POINTER_KW) || (self.at(WITH_KW) && self.nth_significant(1) == Some(POINTER_KW)) {
+            self.skip_ws();
+            self.builder.start_node(POINTER_PHRASE.into());
+            self.eat(WITH_KW);
+            self.eat(POINTER_KW);
+            self.parse_data_ref();
+            self.builder.finish_node();
+        }
+    }
+
+    /// `UNSTRING identifier [DELIMITED [BY] [ALL] value [OR [ALL] value]...]
+    /// INTO receiver... [WITH POINTER identifier] [TALLYING [IN] identifier]
+    /// [ON OVERFLOW ...] [NOT ON OVERFLOW ...]`
+    fn parse_unstring_stmt(&mut self) {
+        self.builder.start_node(UNSTRING_STMT.into());
+        self.bump(); // UNSTRING
+        self.parse_data_ref();
+        if self.at(DELIMITED_KW) {
+            self.skip_ws();
+            self.builder.start_node(DELIMITED_PHRASE.into());
+            self.bump();
+            self.eat(BY_KW);
+            loop {
+                self.skip_ws();
+                self.builder.start_node(UNSTRING_DELIMITER.into());
+                // ALL belongs to the phrase, not to a literal
+                self.eat(ALL_KW);
+                self.parse_operand();
+                self.builder.finish_node();
+                if !self.eat(OR_KW) {
+                    break;
+                }
+            }
+            self.builder.finish_node();
+        }
+
+        self.skip_ws();
+        self.expect(INTO_KW);
+        if !self.at(IDENT) {
+            self.errors.push(format!("Expected receiving item after INTO, found {:?}", self.peek_significant()));
+        }
+        while self.at(IDENT) {
+            self.skip_ws();
+            self.builder.start_node(UNSTRING_RECEIVER.into());
+            self.parse_data_ref();
+            if self.eat(DELIMITER_KW) {
+                self.eat(IN_KW);
+                self.parse_data_ref();
+            }
+            if self.eat(COUNT_KW) {
+                self.eat(IN_KW);
+                self.parse_data_ref();
+            }
+            self.builder.finish_node();
+        }
+
+        self.parse_pointer_phrase();
+        if self.at(TALLYING_KW) {
+            self.skip_ws();
+            self.builder.start_node(TALLYING_PHRASE.into());
+            self.bump();
+            self.eat(IN_KW);
+            self.parse_data_ref();
+            self.builder.finish_node();
+        }
+        self.parse_overflow_phrases();
+        self.eat(END_UNSTRING_KW);
+        self.builder.finish_node();
+    }
+
+    /// `INSPECT identifier` followed by `TALLYING ...` and/or
+    /// `REPLACING ...`, or by `CONVERTING value TO value [boundaries]`
+    fn parse_inspect_stmt(&mut self) {
+        self.builder.start_node(INSPECT_STMT.into());
+        self.bump(); // INSPECT
+        self.parse_data_ref();
+
+        if self.at(TALLYING_KW) {
+            self.skip_ws();
+            self.bump();
+            if !self.at(IDENT) {
+                self.errors.push(format!("Expected count field after TALLYING, found {:?}", self.peek_significant()));
+            }
+            while self.at(IDENT) {
+                self.skip_ws();
+                self.builder.start_node(TALLYING_CLAUSE.into());
+                self.parse_data_ref();
+                self.skip_ws();
+                self.expect(FOR_KW);
+                while self.at(CHARACTERS_KW) || self.at(ALL_KW) || self.at(LEADING_KW) {
+                    self.parse_inspect_group(false);
+                }
+                self.builder.finish_node();
+            }
+        }
+        if self.at(REPLACING_KW) {
+            self.skip_ws();
+            self.builder.start_node(REPLACING_PHRASE.into());
+            self.bump();
+            while self.at(CHARACTERS_KW) || self.at(ALL_KW) || self.at(LEADING_KW) || self.at(FIRST_KW) {
+                self.parse_inspect_group(true);
+            }
+            self.builder.finish_node();
+        } else if self.at(CONVERTING_KW) {
+            self.skip_ws();
+            self.builder.start_node(CONVERTING_PHRASE.into());
+            self.bump();
+            self.parse_operand();
+            self.skip_ws();
+            self.expect(TO_KW);
+            self.parse_operand();
+            self.parse_inspect_boundaries();
+            self.builder.finish_node();
+        }
+        self.builder.finish_node();
+    }
+
+    /// `CHARACTERS [BY value] [boundaries]` or `{ALL | LEADING | FIRST}`
+    /// followed by `value [BY value] [boundaries]` items.
+    fn parse_inspect_group(&mut self, replacing: bool) {
+        self.skip_ws();
+        self.builder.start_node(INSPECT_GROUP.into());
+        let characters = self.current() == Some(CHARACTERS_KW);
+        self.bump(); // CHARACTERS, ALL, LEADING or FIRST
+        loop {
+            self.skip_ws();
+            self.builder.start_node(INSPECT_ITEM.into());
+            if !characters {
+                self.parse_operand();
+            }
+            if replacing {
+                self.skip_ws();
+                self.expect(BY_KW);
+                self.parse_operand();
+            }
+            self.parse_inspect_boundaries();
+            self.builder.finish_node();
+            if characters || !self.at_inspect_item() {
+                break;
+            }
+        }
+        self.builder.finish_node();
+    }
+
+    /// Whether another search value of the current group follows, rather
+    /// than a new group or the count field of a new TALLYING clause.
+    fn at_inspect_item(&self) -> bool {
+        match self.peek_significant() {
+            Some(IDENT) => !self.data_ref_followed_by(FOR_KW),
+            Some(ALL_KW) => false,
+            Some(k) => k.is_literal_start(),
+            None => false,
+        }
+    }
+
+    /// Whether the data reference at the next significant token, including
+    /// any parenthesised subscripts, is followed by `kind`.
+    fn data_ref_followed_by(&self, kind: SyntaxKind) -> bool {
+        let mut n = 1;
+        let mut depth = 0;
+        while let Some(k) = self.nth_significant(n) {
+            match k {
+                L_PAREN => depth += 1,
+                R_PAREN if depth > 0 => depth -= 1,
+                _ if depth > 0 => {}
+                _ => return k == kind,
+            }
+            n += 1;
+        }
+        false
+    }
+
+    fn parse_inspect_boundaries(&mut self) {
+        while self.at(BEFORE_KW) || self.at(AFTER_KW) {
+            self.skip_ws();
+            self.builder.start_node(INSPECT_BOUNDARY.into());
+            self.bump();
+            self.eat(INITIAL_KW);
+            self.parse_operand();
+            self.builder.finish_node();
+        }
+    }
+
+    /// `OPEN {INPUT | OUTPUT | I-O | EXTEND} file...`, repeated
+    fn parse_open_stmt(&mut self) {
+        self.builder.start_node(OPEN_STMT.into());
+        self.bump(); // OPEN
+        let at_mode = |p: &Self| matches!(p.peek_significant(), Some(INPUT_KW | OUTPUT_KW | I_O_KW | EXTEND_KW));
+        if !at_mode(self) {
+            self.errors.push(format!("Expected open mode, found {:?}", self.peek_significant()));
+        }
+        while at_mode(self) {
+            self.skip_ws();
+            self.builder.start_node(OPEN_GROUP.into());
+            self.bump();
+            self.skip_ws();
+            self.expect(IDENT);
+            loop {
+                // Options that only matter for tapes
+                if !self.eat(REVERSED_KW) && (self.eat(WITH_KW) || self.at(NO_KW)) {
+                    self.eat(NO_KW);
+                    self.skip_ws();
+                    self.expect(REWIND_KW);
+                }
+                if !self.eat(IDENT) {
+                    break;
+                }
+            }
+            self.builder.finish_node();
+        }
+        self.builder.finish_node();
+    }
+
+    /// `CLOSE file [REEL | UNIT [FOR REMOVAL] | [WITH] NO REWIND | [WITH] LOCK]...`
+    fn parse_close_stmt(&mut self) {
+        self.builder.start_node(CLOSE_STMT.into());
+        self.bump(); // CLOSE
+        self.skip_ws();
+        self.expect(IDENT);
+        loop {
+            if self.eat(REEL_KW) || self.eat(UNIT_KW) {
+                if self.eat(FOR_KW) {
+                    self.skip_ws();
+                    self.expect(REMOVAL_KW);
+                }
+            } else if self.eat(WITH_KW) || self.at(NO_KW) || self.at(LOCK_KW) {
+                if self.eat(NO_KW) {
+                    self.skip_ws();
+                    self.expect(REWIND_KW);
+                } else {
+                    self.skip_ws();
+                    self.expect(LOCK_KW);
+                }
+            } else if !self.eat(IDENT) {
+                break;
+            }
+        }
+        self.builder.finish_node();
+    }
+
+    /// `READ file [NEXT | PREVIOUS] [RECORD] [INTO identifier] [KEY IS name]`
+    /// followed by AT END or INVALID KEY phrases.
+    fn parse_read_stmt(&mut self) {
+        self.builder.start_node(READ_STMT.into());
+        self.bump(); // READ
+        self.skip_ws();
+        self.expect(IDENT);
+        if !self.eat(NEXT_KW) {
+            self.eat(PREVIOUS_KW);
+        }
+        self.eat(RECORD_KW);
+        if self.at(INTO_KW) {
+            self.parse_into_phrase();
+        }
+        if self.at(KEY_KW) {
+            self.parse_key_phrase();
+        }
+        self.parse_at_end_phrases();
+        self.parse_invalid_key_phrases();
+        self.eat(END_READ_KW);
+        self.builder.finish_node();
+    }
+
+    /// `KEY [IS] name`, or `KEY relational-operator name` for START
+    fn parse_key_phrase(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(KEY_PHRASE.into());
+        self.bump(); // KEY
+        if self.rel_op_at(0) {
+            self.parse_rel_op();
+        } else {
+            self.eat(IS_KW);
+        }
+        self.parse_data_ref();
+        self.builder.finish_node();
+    }
+
+    fn parse_from_phrase(&mut self) {
+        if self.at(FROM_KW) {
+            self.skip_ws();
+            self.builder.start_node(FROM_PHRASE.into());
+            self.bump();
+            self.parse_operand();
+            self.builder.finish_node();
+        }
+    }
+
+    /// `WRITE record [FROM identifier] [{BEFORE | AFTER} [ADVANCING]
+    /// {n [LINES] | PAGE | mnemonic}]` followed by END-OF-PAGE or INVALID
+    /// KEY phrases.
+    fn parse_write_stmt(&mut self) {
+        self.builder.start_node(WRITE_STMT.into());
+        self.bump(); // WRITE
+        self.parse_data_ref();
+        self.parse_from_phrase();
+        if self.at(BEFORE_KW) || self.at(AFTER_KW) {
+            self.skip_ws();
+            self.builder.start_node(ADVANCING_PHRASE.into());
+            self.bump();
+            self.eat(ADVANCING_KW);
+            if !self.eat(PAGE_KW) {
+                self.parse_operand();
+                self.eat(LINE_KW);
+            }
+            self.builder.finish_node();
+        }
+        self.parse_handler_phrases([AT_END_OF_PAGE, NOT_AT_END_OF_PAGE], AT_KW, END_OF_PAGE_KW, None);
+        self.parse_invalid_key_phrases();
+        self.eat(END_WRITE_KW);
+        self.builder.finish_node();
+    }
+
+    /// `REWRITE record [FROM identifier]` with INVALID KEY phrases
+    fn parse_rewrite_stmt(&mut self) {
+        self.builder.start_node(REWRITE_STMT.into());
+        self.bump(); // REWRITE
+        self.parse_data_ref();
+        self.parse_from_phrase();
+        self.parse_invalid_key_phrases();
+        self.eat(END_REWRITE_KW);
+        self.builder.finish_node();
+    }
+
+    /// `DELETE file [RECORD]` with INVALID KEY phrases
+    fn parse_delete_stmt(&mut self) {
+        self.builder.start_node(DELETE_STMT.into());
+        self.bump(); // DELETE
+        self.skip_ws();
+        self.expect(IDENT);
+        self.eat(RECORD_KW);
+        self.parse_invalid_key_phrases();
+        self.eat(END_DELETE_KW);
+        self.builder.finish_node();
+    }
+
+    /// `START file [KEY relational-operator name]` with INVALID KEY phrases
+    fn parse_start_stmt(&mut self) {
+        self.builder.start_node(START_STMT.into());
+        self.bump(); // START
+        self.skip_ws();
+        self.expect(IDENT);
+        if self.at(KEY_KW) {
+            self.parse_key_phrase();
+        }
+        self.parse_invalid_key_phrases();
+        self.eat(END_START_KW);
+        self.builder.finish_node();
+    }
+
+    /// `CALL program [USING ...] [RETURNING identifier]` followed by ON
+    /// EXCEPTION or ON OVERFLOW phrases. The program is a literal, or an
+    /// identifier holding the name for a dynamic call.
+    fn parse_call_stmt(&mut self) {
+        self.builder.start_node(CALL_STMT.into());
+        self.bump(); // CALL
+        self.parse_operand();
+        if self.at(USING_KW) {
+            self.parse_using_phrase();
+        }
+        if self.at(RETURNING_KW) {
+            self.parse_returning_phrase();
+        }
+        self.parse_handler_phrases([ON_EXCEPTION, NOT_ON_EXCEPTION], ON_KW, EXCEPTION_KW, None);
+        self.parse_overflow_phrases();
+        self.eat(END_CALL_KW);
+        self.builder.finish_node();
+    }
+
+    /// `USING {[BY] {REFERENCE | CONTENT | VALUE}] {operand | OMITTED}...}...`.
+    /// A BY phrase applies to every argument up to the next one.
+    fn parse_using_phrase(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(USING_PHRASE.into());
+        self.bump(); // USING
+        let at_mode = |p: &Self, n| matches!(p.nth_significant(n), Some(REFERENCE_KW | CONTENT_KW | VALUE_KW));
+        loop {
+            let has_mode = at_mode(self, 0) || (self.at(BY_KW) && at_mode(self, 1));
+            if !has_mode && !self.at_operand() && !self.at(OMITTED_KW) {
+                break;
+            }
+            self.skip_ws();
+            self.builder.start_node(ARGUMENT.into());
+            if has_mode {
+                self.eat(BY_KW);
+                self.skip_ws();
+                self.bump(); // REFERENCE, CONTENT or VALUE
+            }
+            if !self.eat(OMITTED_KW) {
+                self.parse_operand();
+            }
+            self.builder.finish_node();
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_returning_phrase(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(RETURNING_PHRASE.into());
+        self.bump(); // RETURNING
+        self.parse_data_ref();
+        self.builder.finish_node();
+    }
+
+    /// `CANCEL program...`
+    fn parse_cancel_stmt(&mut self) {
+        self.builder.start_node(CANCEL_STMT.into());
+        self.bump(); // CANCEL
+        self.parse_operand();
+        while self.at_operand() {
+            self.parse_operand();
+        }
+        self.builder.finish_node();
+    }
+
+    /// `SEARCH [ALL] table [VARYING identifier] [AT END statements]
+    /// {WHEN condition statements}... [END-SEARCH]`. SEARCH ALL has no
+    /// VARYING and a single WHEN testing keys with EQUAL relations or
+    /// condition-names joined by AND.
+    fn parse_search_stmt(&mut self) {
+        self.builder.start_node(SEARCH_STMT.into());
+        self.bump(); // SEARCH
+        let all = self.eat(ALL_KW);
+        self.parse_data_ref();
+        if self.at(VARYING_KW) {
+            if all {
+                self.errors.push("VARYING is not allowed in SEARCH ALL".to_string());
+            }
+            self.skip_ws();
+            self.builder.start_node(SEARCH_VARYING.into());
+            self.bump();
+            self.parse_data_ref();
+            self.builder.finish_node();
+        }
+        self.parse_at_end_phrases();
+        if !self.at(WHEN_KW) {
+            self.errors.push(format!("Expected WHEN, found {:?}", self.peek_significant()));
+        }
+        let mut whens = 0;
+        while self.at(WHEN_KW) {
+            whens += 1;
+            if all && whens == 2 {
+                self.errors.push("SEARCH ALL takes a single WHEN phrase".to_string());
+            }
+            self.skip_ws();
+            self.builder.start_node(SEARCH_WHEN.into());
+            self.bump(); // WHEN
+            let start = self.pos;
+            self.parse_condition();
+            // A binary search tests keys for equality only
+            if all
+                && self.tokens[start..self.pos].iter().any(|(kind, _)| {
+                    matches!(
+                        kind,
+                        OR_KW | NOT_KW | GREATER_KW | LESS_KW | GREATER | LESS | GREATER_EQUALS | LESS_EQUALS
+                    )
+                })
+            {
+                self.errors
+                    .push("SEARCH ALL conditions must be EQUAL relations or condition-names joined by AND".to_string());
+            }
+            self.parse_branch_statements("WHEN");
+            self.builder.finish_node();
+        }
+        self.eat(END_SEARCH_KW);
+        self.builder.finish_node();
+    }
+
+    /// `SET target... TO value`, `SET index... {UP | DOWN} BY n`, where a
+    /// target may be `ADDRESS OF identifier` and the value TRUE, FALSE or
+    /// `ADDRESS OF identifier`.
+    fn parse_set_stmt(&mut self) {
+        self.builder.start_node(SET_STMT.into());
+        self.bump(); // SET
+        loop {
+            if self.at(ADDRESS_KW) {
+                self.parse_address_of();
+            } else {
+                self.parse_data_ref();
+            }
+            if !self.at(IDENT) && !self.at(ADDRESS_KW) {
+                break;
+            }
+        }
+        self.skip_ws();
+        match self.current() {
+            Some(UP_KW | DOWN_KW) => {
+                self.bump();
+                self.skip_ws();
+                self.expect(BY_KW);
+                self.parse_operand();
+            }
+            _ => {
+                self.expect(TO_KW);
+                match self.peek_significant() {
+                    Some(TRUE_KW | FALSE_KW) => {
+                        self.skip_ws();
+                        self.bump();
+                    }
+                    Some(ADDRESS_KW) => self.parse_address_of(),
+                    _ => self.parse_operand(),
+                }
+            }
+        }
+        self.builder.finish_node();
+    }
+
+    fn parse_address_of(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(ADDRESS_OF.into());
+        self.bump(); // ADDRESS
+        self.skip_ws();
+        self.expect(OF_KW);
+        self.parse_data_ref();
+        self.builder.finish_node();
+    }
+
+    /// `SORT file {ON {ASCENDING | DESCENDING} KEY name...}...
+    /// [WITH DUPLICATES IN ORDER] [COLLATING SEQUENCE IS alphabet]
+    /// {USING file... | INPUT PROCEDURE IS range}
+    /// {GIVING file... | OUTPUT PROCEDURE IS range}`. MERGE has the same
+    /// form without DUPLICATES and INPUT PROCEDURE, and merges at least two
+    /// USING files.
+    fn parse_sort_stmt(&mut self, kind: SyntaxKind) {
+        let merge = kind == MERGE_STMT;
+        self.builder.start_node(kind.into());
+        self.bump(); // SORT or MERGE
+        self.skip_ws();
+        self.expect(IDENT);
+
+        let at_key = |p: &Self| {
+            let n = usize::from(p.at(ON_KW));
+            matches!(p.nth_significant(n), Some(ASCENDING_KW | DESCENDING_KW))
+        };
+        if !at_key(self) {
+            self.errors.push(format!("Expected sort key, found {:?}", self.peek_significant()));
+        }
+        while at_key(self) {
+            self.skip_ws();
+            self.builder.start_node(SORT_KEY.into());
+            self.eat(ON_KW);
+            self.skip_ws();
+            self.bump(); // ASCENDING or DESCENDING
+            self.eat(KEY_KW);
+            self.eat(IS_KW);
+            self.parse_data_ref();
+            while self.at(IDENT) {
+                self.parse_data_ref();
+            }
+            self.builder.finish_node();
+        }
+
+        if self.eat(WITH_KW) || self.at(DUPLICATES_KW) {
+            if merge {
+                self.errors.push("WITH DUPLICATES is not allowed in MERGE".to_string());
+            }
+            self.skip_ws();
+            self.expect(DUPLICATES_KW);
+            self.eat(IN_KW);
+            self.eat(ORDER_KW);
+        }
+        if self.eat(COLLATING_KW) || self.at(SEQUENCE_KW) {
+            self.skip_ws();
+            self.expect(SEQUENCE_KW);
+            self.eat(IS_KW);
+            self.skip_ws();
+            self.expect(IDENT);
+        }
+
+        if self.at(USING_KW) {
+            let files = self.parse_file_list(SORT_USING);
+            if merge && files < 2 {
+                self.errors.push("MERGE needs at least two USING files".to_string());
+            }
+        } else if self.at(INPUT_KW) {
+            if merge {
+                self.errors.push("INPUT PROCEDURE is not allowed in MERGE".to_string());
+            }
+            self.parse_sort_procedure(INPUT_PROCEDURE);
+        } else {
+            self.errors.push(format!("Expected USING or INPUT PROCEDURE, found {:?}", self.peek_significant()));
+        }
+        if self.at(GIVING_KW) {
+            self.parse_file_list(SORT_GIVING);
+        } else if self.at(OUTPUT_KW) {
+            self.parse_sort_procedure(OUTPUT_PROCEDURE);
+        } else {
+            self.errors.push(format!("Expected GIVING or OUTPUT PROCEDURE, found {:?}", self.peek_significant()));
+        }
+        self.builder.finish_node();
+    }
+
+    /// `USING file...` or `GIVING file...`, returning the number of files.
+    fn parse_file_list(&mut self, kind: SyntaxKind) -> usize {
+        self.skip_ws();
+        self.builder.start_node(kind.into());
+        self.bump(); // USING or GIVING
+        self.skip_ws();
+        let mut files = usize::from(self.current() == Some(IDENT));
+        self.expect(IDENT);
+        while self.eat(IDENT) {
+            files += 1;
+        }
+        self.builder.finish_node();
+        files
+    }
+
+    /// `{INPUT | OUTPUT} PROCEDURE [IS] procedure-name [THRU procedure-name]`
+    fn parse_sort_procedure(&mut self, kind: SyntaxKind) {
+        self.skip_ws();
+        self.builder.start_node(kind.into());
+        self.bump(); // INPUT or OUTPUT
+        self.skip_ws();
+        self.expect(PROCEDURE_KW);
+        self.eat(IS_KW);
+        self.parse_procedure_range();
+        self.builder.finish_node();
+    }
+
+    /// `RELEASE record [FROM identifier]`
+    fn parse_release_stmt(&mut self) {
+        self.builder.start_node(RELEASE_STMT.into());
+        self.bump(); // RELEASE
+        self.parse_data_ref();
+        self.parse_from_phrase();
+        self.builder.finish_node();
+    }
+
+    /// `RETURN file [RECORD] [INTO identifier]` followed by AT END phrases
+    fn parse_return_stmt(&mut self) {
+        self.builder.start_node(RETURN_STMT.into());
+        self.bump(); // RETURN
+        self.skip_ws();
+        self.expect(IDENT);
+        self.eat(RECORD_KW);
+        if self.at(INTO_KW) {
+            self.parse_into_phrase();
+        }
+        self.parse_at_end_phrases();
+        self.eat(END_RETURN_KW);
+        self.builder.finish_node();
+    }
+
+    /// `ANY`, `TRUE`, `FALSE`, a condition or `[NOT] value [THRU value]`
+    fn parse_when_object(&mut self) {
+        self.skip_ws();
+        self.builder.start_node(WHEN_OBJECT.into());
+        match self.current() {
+            Some(ANY_KW | TRUE_KW | FALSE_KW) => self.bump(),
+            _ if self.condition_ahead(0) => self.parse_condition(),
+            _ => {
+                self.eat(NOT_KW);
+                self.parse_expr();
+                if self.eat(THRU_KW) {
+                    self.parse_expr();
+                }
+            }
+        }
+        self.builder.finish_node();
+    }
+
+    /// Whether the EVALUATE subject or object starting at the `n`th
+    /// significant token is a condition rather than a value.
+    fn condition_ahead(&self, n: usize) -> bool {
+        let mut i = n;
+        while let Some(kind) = self.nth_significant(i) {
+            match kind {
+                ALSO_KW | WHEN_KW | THRU_KW | END_EVALUATE_KW | DOT => return false,
+                k if k.is_verb() => return false,
+                NOT_KW if i > n => return true,
+                AND_KW | OR_KW | IS_KW | NUMERIC_KW | ALPHABETIC_KW | ALPHABETIC_LOWER_KW
+                | ALPHABETIC_UPPER_KW | POSITIVE_KW | NEGATIVE_KW => return true,
+                _ if self.rel_op_at(i) => return true,
+                _ => {}
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// `DISPLAY operand... [UPON device] [WITH NO ADVANCING]`
+    fn parse_display_stmt(&mut self) {
+        self.builder.start_node(DISPLAY_STMT.into());
+        self.bump(); // DISPLAY
+        self.parse_operand();
+        while self.at_operand() {
+            self.parse_operand();
+        }
+        if self.eat(UPON_KW) {
+            self.skip_ws();
+            self.expect(IDENT);
+        }
+        if self.eat(WITH_KW) || self.at(NO_KW) {
+            self.skip_ws();
+            self.expect(NO_KW);
+            self.skip_ws();
+            self.expect(ADVANCING_KW);
+        }
+        self.builder.finish_node();
+    }
+
+    /// `ACCEPT identifier [FROM source]`, where the source is DATE, DAY,
+    /// DAY-OF-WEEK, TIME, `ENVIRONMENT name` or a device such as CONSOLE.
+    fn parse_accept_stmt(&mut self) {
+        self.builder.start_node(ACCEPT_STMT.into());
+        self.bump(); // ACCEPT
+        self.parse_data_ref();
+        if self.eat(FROM_KW) {
+            self.skip_ws();
+            match self.current() {
+                Some(DATE_KW) => {
+                    self.bump();
+                    self.eat_word("YYYYMMDD");
+                }
+                Some(DAY_KW) => {
+                    self.bump();
+                    self.eat_word("YYYYDDD");
+                }
+                Some(ENVIRONMENT_KW) => {
+                    self.bump();
+                    self.parse_operand();
+                }
+                Some(DAY_OF_WEEK_KW | TIME_KW | IDENT) => self.bump(),
+                found => self.errors.push(format!("Expected ACCEPT source, found {:?}", found)),
+            }
+        }
+        self.builder.finish_node();
+    }
+}
+
+/// Whether an entry with level `child` following one with level `parent`
+/// belongs to it. Level 88 entries are condition names of the preceding item;
+/// levels 66 and 77 always start at the section level.
+fn is_subordinate(parent: u8, child: u8) -> bool {
+    match (parent, child) {
+        (66 | 88, _) => false,
+        (_, 88) => true,
+        (77, _) | (_, 66 | 77) => false,
+        _ => child > parent && child <= 49,
+    }
+}
+
+pub fn parse(text: &str) -> Parse {
+    parse_with_options(text, SourceOptions::default())
+}
+
+pub fn parse_with_options(text: &str, options: SourceOptions) -> Parse {
+    let tokens = lex(text, options);
+    let mut parser = Parser::new(tokens);
+    parser.decimal_point_is_comma = options.decimal_point_is_comma;
+    parser.parse()
+}
+
+// ============================================================================
+// AST wrappers
+// ============================================================================
+
+/// The block of comment lines directly above `node`, in source order.
+///
+/// The block ends at a blank line or at a line holding program text. A
+/// floating comment that trails code belongs to that code, not to `node`.
+pub fn leading_comments(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    let mut comments = Vec::new();
+    let mut newlines = 0;
+    let mut token = node.first_token().and_then(|t| t.prev_token());
+    while let Some(t) = token {
+        match t.kind() {
+            NEWLINE => {
+                newlines += 1;
+                if newlines > 1 {
+                    break;
+                }
+            }
+            COMMENT if starts_line(&t) => {
+                newlines = 0;
+                comments.push(t.clone());
+            }
+            kind if kind.is_trivia() && kind != COMMENT => {}
+            _ => break,
+        }
+        token = t.prev_token();
+    }
+    comments.reverse();
+    comments
+}
+
+fn starts_line(token: &SyntaxToken) -> bool {
+    let mut prev = token.prev_token();
+    while let Some(t) = prev {
+        match t.kind() {
+            NEWLINE => return true,
+            COMMENT => return false,
+            kind if kind.is_trivia() => prev = t.prev_token(),
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Text of a COMMENT token without the `*>` marker and surrounding blanks.
+pub fn comment_text(token: &SyntaxToken) -> String {
+    let text = token.text();
+    text.strip_prefix("*>").unwrap_or(text).trim().to_string()
+}
+
+macro_rules! ast_node {
+    ($ast:ident, $kind:ident) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $ast(SyntaxNode);
+
+        impl $ast {
+            pub fn cast(node: SyntaxNode) -> Option<Self> {
+                if node.kind() == $kind {
+                    Some(Self(node))
+                } else {
+                    None
+                }
+            }
+
+            pub fn syntax(&self) -> &SyntaxNode {
+                &self.0
+            }
+
+            /// Text of the comment block preceding this node.
+            pub fn leading_comments(&self) -> Vec<String> {
+                leading_comments(&self.0).iter().map(comment_text).collect()
+            }
+        }
+    };
+}
+
+ast_node!(Root, ROOT);
+ast_node!(IdentificationDivision, IDENTIFICATION_DIVISION);
+ast_node!(ProgramIdClause, PROGRAM_ID_CLAUSE);
+ast_node!(IdentificationParagraph, IDENTIFICATION_PARAGRAPH);
+ast_node!(ProcedureDivision, PROCEDURE_DIVISION);
+ast_node!(Section, SECTION);
+ast_node!(Paragraph, PARAGRAPH);
+ast_node!(Sentence, SENTENCE);
+ast_node!(DisplayStmt, DISPLAY_STMT);
+ast_node!(MoveStmt, MOVE_STMT);
+ast_node!(DataRef, DATA_REF);
+ast_node!(RefMod, REF_MOD);
+ast_node!(ComputeStmt, COMPUTE_STMT);
+ast_node!(Receiver, RECEIVER);
+ast_node!(BinExpr, BIN_EXPR);
+ast_node!(PrefixExpr, PREFIX_EXPR);
+ast_node!(ParenExpr, PAREN_EXPR);
+ast_node!(IfStmt, IF_STMT);
+ast_node!(RelationCondition, RELATION_CONDITION);
+ast_node!(ClassCondition, CLASS_CONDITION);
+ast_node!(SignCondition, SIGN_CONDITION);
+ast_node!(ConditionName, CONDITION_NAME);
+ast_node!(NotCondition, NOT_CONDITION);
+ast_node!(CombinedCondition, COMBINED_CONDITION);
+ast_node!(ParenCondition, PAREN_CONDITION);
+ast_node!(EvaluateStmt, EVALUATE_STMT);
+ast_node!(EvaluateSubject, EVALUATE_SUBJECT);
+ast_node!(WhenBranch, WHEN_BRANCH);
+ast_node!(WhenObject, WHEN_OBJECT);
+ast_node!(PerformStmt, PERFORM_STMT);
+ast_node!(ProcedureRange, PROCEDURE_RANGE);
+ast_node!(VaryingPhrase, VARYING_PHRASE);
+ast_node!(GoToStmt, GO_TO_STMT);
+ast_node!(ExitStmt, EXIT_STMT);
+ast_node!(StopStmt, STOP_STMT);
+ast_node!(GobackStmt, GOBACK_STMT);
+ast_node!(ContinueStmt, CONTINUE_STMT);
+ast_node!(NextSentenceStmt, NEXT_SENTENCE_STMT);
+ast_node!(AlterStmt, ALTER_STMT);
+ast_node!(StringStmt, STRING_STMT);
+ast_node!(StringSource, STRING_SOURCE);
+ast_node!(UnstringStmt, UNSTRING_STMT);
+ast_node!(UnstringDelimiter, UNSTRING_DELIMITER);
+ast_node!(UnstringReceiver, UNSTRING_RECEIVER);
+ast_node!(InspectStmt, INSPECT_STMT);
+ast_node!(TallyingClause, TALLYING_CLAUSE);
+ast_node!(ConvertingPhrase, CONVERTING_PHRASE);
+ast_node!(InspectGroup, INSPECT_GROUP);
+ast_node!(InspectItem, INSPECT_ITEM);
+ast_node!(InspectBoundary, INSPECT_BOUNDARY);
+ast_node!(OpenStmt, OPEN_STMT);
+ast_node!(CloseStmt, CLOSE_STMT);
+ast_node!(ReadStmt, READ_STMT);
+ast_node!(WriteStmt, WRITE_STMT);
+ast_node!(RewriteStmt, REWRITE_STMT);
+ast_node!(DeleteStmt, DELETE_STMT);
+ast_node!(StartStmt, START_STMT);
+ast_node!(AdvancingPhrase, ADVANCING_PHRASE);
+ast_node!(CallStmt, CALL_STMT);
+ast_node!(CancelStmt, CANCEL_STMT);
+ast_node!(AcceptStmt, ACCEPT_STMT);
+ast_node!(SearchStmt, SEARCH_STMT);
+ast_node!(SearchWhen, SEARCH_WHEN);
+ast_node!(SetStmt, SET_STMT);
+ast_node!(SortStmt, SORT_STMT);
+ast_node!(MergeStmt, MERGE_STMT);
+ast_node!(ReleaseStmt, RELEASE_STMT);
+ast_node!(ReturnStmt, RETURN_STMT);
+ast_node!(FunctionCall, FUNCTION_CALL);
+ast_node!(Literal, LITERAL);
+ast_node!(DataDivision, DATA_DIVISION);
+ast_node!(FileSection, FILE_SECTION);
+ast_node!(WorkingStorageSection, WORKING_STORAGE_SECTION);
+ast_node!(LocalStorageSection, LOCAL_STORAGE_SECTION);
+ast_node!(LinkageSection, LINKAGE_SECTION);
+ast_node!(FileDescription, FILE_DESCRIPTION);
+ast_node!(DataItem, DATA_ITEM);
+ast_node!(PictureClause, PICTURE_CLAUSE);
+ast_node!(UsageClause, USAGE_CLAUSE);
+ast_node!(ValueClause, VALUE_CLAUSE);
+ast_node!(OccursClause, OCCURS_CLAUSE);
+ast_node!(RedefinesClause, REDEFINES_CLAUSE);
+ast_node!(RenamesClause, RENAMES_CLAUSE);
+ast_node!(JustifiedClause, JUSTIFIED_CLAUSE);
+ast_node!(SignClause, SIGN_CLAUSE);
+ast_node!(SynchronizedClause, SYNCHRONIZED_CLAUSE);
+ast_node!(EnvironmentDivision, ENVIRONMENT_DIVISION);
+ast_node!(ConfigurationSection, CONFIGURATION_SECTION);
+ast_node!(SpecialNamesParagraph, SPECIAL_NAMES_PARAGRAPH);
+ast_node!(InputOutputSection, INPUT_OUTPUT_SECTION);
+ast_node!(FileControlParagraph, FILE_CONTROL_PARAGRAPH);
+ast_node!(SelectEntry, SELECT_ENTRY);
+
+/// Any statement of the PROCEDURE DIVISION.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Stmt(SyntaxNode);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StmtKind {
+    Display(DisplayStmt),
+    Move(MoveStmt),
+    Add(ArithmeticStmt),
+    Subtract(ArithmeticStmt),
+    Multiply(ArithmeticStmt),
+    Divide(ArithmeticStmt),
+    Compute(ComputeStmt),
+    If(IfStmt),
+    Evaluate(EvaluateStmt),
+    Perform(PerformStmt),
+    GoTo(GoToStmt),
+    Exit(ExitStmt),
+    Stop(StopStmt),
+    Goback(GobackStmt),
+    Continue(ContinueStmt),
+    NextSentence(NextSentenceStmt),
+    Alter(AlterStmt),
+    String(StringStmt),
+    Unstring(UnstringStmt),
+    Inspect(InspectStmt),
+    Open(OpenStmt),
+    Close(CloseStmt),
+    Read(ReadStmt),
+    Write(WriteStmt),
+    Rewrite(RewriteStmt),
+    Delete(DeleteStmt),
+    Start(StartStmt),
+    Call(CallStmt),
+    Cancel(CancelStmt),
+    Accept(AcceptStmt),
+    Search(SearchStmt),
+    Set(SetStmt),
+    Sort(SortStmt),
+    Merge(MergeStmt),
+    Release(ReleaseStmt),
+    Return(ReturnStmt),
+}
+
+impl Stmt {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if node.kind().is_statement() {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn syntax(&self) -> &SyntaxNode {
+        &self.0
+    }
+
+    pub fn kind(&self) -> StmtKind {
+        let node = self.0.clone();
+        match node.kind() {
+            DISPLAY_STMT => StmtKind::Display(DisplayStmt(node)),
+            MOVE_STMT => StmtKind::Move(MoveStmt(node)),
+            ADD_STMT => StmtKind::Add(ArithmeticStmt(node)),
+            SUBTRACT_STMT => StmtKind::Subtract(ArithmeticStmt(node)),
+            MULTIPLY_STMT => StmtKind::Multiply(ArithmeticStmt(node)),
+            DIVIDE_STMT => StmtKind::Divide(ArithmeticStmt(node)),
+            COMPUTE_STMT => StmtKind::Compute(ComputeStmt(node)),
+            IF_STMT => StmtKind::If(IfStmt(node)),
+            EVALUATE_STMT => StmtKind::Evaluate(EvaluateStmt(node)),
+            PERFORM_STMT => StmtKind::Perform(PerformStmt(node)),
+            GO_TO_STMT => StmtKind::GoTo(GoToStmt(node)),
+            EXIT_STMT => StmtKind::Exit(ExitStmt(node)),
+            STOP_STMT => StmtKind::Stop(StopStmt(node)),
+            GOBACK_STMT => StmtKind::Goback(GobackStmt(node)),
+            CONTINUE_STMT => StmtKind::Continue(ContinueStmt(node)),
+            NEXT_SENTENCE_STMT => StmtKind::NextSentence(NextSentenceStmt(node)),
+            ALTER_STMT => StmtKind::Alter(AlterStmt(node)),
+            STRING_STMT => StmtKind::String(StringStmt(node)),
+            UNSTRING_STMT => StmtKind::Unstring(UnstringStmt(node)),
+            INSPECT_STMT => StmtKind::Inspect(InspectStmt(node)),
+            OPEN_STMT => StmtKind::Open(OpenStmt(node)),
+            CLOSE_STMT => StmtKind::Close(CloseStmt(node)),
+            READ_STMT => StmtKind::Read(ReadStmt(node)),
+            WRITE_STMT => StmtKind::Write(WriteStmt(node)),
+            REWRITE_STMT => StmtKind::Rewrite(RewriteStmt(node)),
+            DELETE_STMT => StmtKind::Delete(DeleteStmt(node)),
+            START_STMT => StmtKind::Start(StartStmt(node)),
+            CALL_STMT => StmtKind::Call(CallStmt(node)),
+            CANCEL_STMT => StmtKind::Cancel(CancelStmt(node)),
+            ACCEPT_STMT => StmtKind::Accept(AcceptStmt(node)),
+            SEARCH_STMT => StmtKind::Search(SearchStmt(node)),
+            SET_STMT => StmtKind::Set(SetStmt(node)),
+            SORT_STMT => StmtKind::Sort(SortStmt(node)),
+            MERGE_STMT => StmtKind::Merge(MergeStmt(node)),
+            RELEASE_STMT => StmtKind::Release(ReleaseStmt(node)),
+            RETURN_STMT => StmtKind::Return(ReturnStmt(node)),
+            kind => unreachable!("{:?} is not a statement", kind),
+        }
+    }
+
+    /// Whether execution may continue with the next statement. False for
+    /// jumps and for statements that end the paragraph, section, PERFORM
+    /// or run unit.
+    pub fn falls_through(&self) -> bool {
+        match self.kind() {
+            StmtKind::GoTo(_) | StmtKind::Goback(_) | StmtKind::NextSentence(_) => false,
+            StmtKind::Stop(stop) => !stop.is_run(),
+            StmtKind::Exit(exit) => exit.kind() == ExitKind::Plain,
+            _ => true,
+        }
+    }
+
+    /// The procedures a statement hands control to before continuing with
+    /// the next statement: those of an out-of-line PERFORM and the input
+    /// and output procedures of SORT and MERGE.
+    pub fn invoked_procedures(&self) -> Vec<ProcedureRange> {
+        match self.kind() {
+            StmtKind::Perform(perform) => perform.procedure().into_iter().collect(),
+            StmtKind::Sort(_) | StmtKind::Merge(_) => self
+                .0
+                .children()
+                .filter(|n| matches!(n.kind(), INPUT_PROCEDURE | OUTPUT_PROCEDURE))
+                .filter_map(|n| n.children().find_map(ProcedureRange::cast))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The files a file I/O statement acts on, linked to their SELECT
+    /// entry and FD. WRITE, REWRITE and RELEASE name a record, which is
+    /// looked up in the FDs; they have no file when the record is not an FD
+    /// record. Empty for other statements.
+    pub fn files(&self) -> Vec<FileRef> {
+        let names = match self.kind() {
+            StmtKind::Open(open) => open.files().into_iter().map(|(_, name)| name).collect(),
+            StmtKind::Close(close) => close.files(),
+            StmtKind::Read(_) | StmtKind::Delete(_) | StmtKind::Start(_) | StmtKind::Return(_) => {
+                child_token(&self.0, IDENT).map(|t| t.text().to_string()).into_iter().collect()
+            }
+            StmtKind::Sort(_) | StmtKind::Merge(_) => sort_files(&self.0),
+            StmtKind::Write(_) | StmtKind::Rewrite(_) | StmtKind::Release(_) => {
+                io_record(&self.0)
+                    .and_then(|record| enclosing_data_division(&self.0)?.resolve(&record).ok())
+                    .and_then(|item| item.syntax().ancestors().find_map(FileDescription::cast))
+                    .and_then(|fd| fd.name())
+                    .into_iter()
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        let root = self.0.ancestors().last().and_then(Root::cast);
+        names
+            .into_iter()
+            .map(|name| FileRef {
+                select: root.as_ref().and_then(|r| r.select_entry(&name)),
+                fd: root.as_ref().and_then(|r| r.file_description(&name)),
+                name,
+            })
+            .collect()
+    }
+}
+
+/// A file named by an I/O statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRef {
+    pub name: String,
+    pub select: Option<SelectEntry>,
+    pub fd: Option<FileDescription>,
+}
+
+fn child_token(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(|el| el.into_token())
+        .find(|t| t.kind() == kind)
+}
+
+impl Root {
+    pub fn identification_division(&self) -> Option<IdentificationDivision> {
+        self.0.children().find_map(IdentificationDivision::cast)
+    }
+
+    pub fn program_id(&self) -> Option<ProgramIdClause> {
+        self.0
+            .children()
+            .find_map(ProgramIdClause::cast)
+            .or_else(|| self.identification_division()?.program_id())
+    }
+
+    pub fn environment_division(&self) -> Option<EnvironmentDivision> {
+        self.0.children().find_map(EnvironmentDivision::cast)
+    }
+
+    pub fn data_division(&self) -> Option<DataDivision> {
+        self.0.children().find_map(DataDivision::cast)
+    }
+
+    pub fn procedure_division(&self) -> Option<ProcedureDivision> {
+        self.0.children().find_map(ProcedureDivision::cast)
+    }
+}
+
+impl Root {
+    pub fn select_entry(&self, file_name: &str) -> Option<SelectEntry> {
+        let file_control = self.environment_division()?.input_output_section()?.file_control()?;
+        file_control
+            .select_entries()
+            .find(|e| e.file_name().is_some_and(|n| n.eq_ignore_ascii_case(file_name)))
+    }
+
+    pub fn file_description(&self, file_name: &str) -> Option<FileDescription> {
+        self.data_division()?
+            .file_section()?
+            .file_descriptions()
+            .find(|fd| fd.name().is_some_and(|n| n.eq_ignore_ascii_case(file_name)))
+    }
+
+    /// The FD that describes the record `record_name`.
+    pub fn record_file(&self, record_name: &str) -> Option<FileDescription> {
+        self.data_division()?.file_section()?.file_descriptions().find(|fd| {
+            fd.records()
+                .any(|r| r.name().is_some_and(|n| n.eq_ignore_ascii_case(record_name)))
+        })
+    }
+}
+
+impl EnvironmentDivision {
+    pub fn configuration_section(&self) -> Option<ConfigurationSection> {
+        self.0.children().find_map(ConfigurationSection::cast)
+    }
+
+    pub fn input_output_section(&self) -> Option<InputOutputSection> {
+        self.0.children().find_map(InputOutputSection::cast)
+    }
+}
+
+/// First word of a paragraph body, e.g. the computer name.
+fn paragraph_name_entry(node: &SyntaxNode, kind: SyntaxKind) -> Option<String> {
+    let paragraph = node.children().find(|n| n.kind() == kind)?;
+    child_token(&paragraph, IDENT).map(|t| t.text().to_string())
+}
+
+impl ConfigurationSection {
+    pub fn source_computer(&self) -> Option<String> {
+        paragraph_name_entry(&self.0, SOURCE_COMPUTER_PARAGRAPH)
+    }
+
+    pub fn object_computer(&self) -> Option<String> {
+        paragraph_name_entry(&self.0, OBJECT_COMPUTER_PARAGRAPH)
+    }
+
+    pub fn special_names(&self) -> Option<SpecialNamesParagraph> {
+        self.0.children().find_map(SpecialNamesParagraph::cast)
+    }
+}
+
+impl SpecialNamesParagraph {
+    pub fn decimal_point_is_comma(&self) -> bool {
+        self.0.children().any(|n| n.kind() == DECIMAL_POINT_CLAUSE)
+    }
+
+    /// `(environment-name, mnemonic-name)` pairs, e.g. `CONSOLE IS CRT`.
+    pub fn mnemonic_names(&self) -> Vec<(String, String)> {
+        self.0
+            .children()
+            .filter(|n| n.kind() == SPECIAL_NAME_CLAUSE)
+            .filter_map(|n| {
+                let mut names = idents(&n).into_iter();
+                Some((names.next()?, names.next()?))
+            })
+            .collect()
+    }
+}
+
+impl InputOutputSection {
+    pub fn file_control(&self) -> Option<FileControlParagraph> {
+        self.0.children().find_map(FileControlParagraph::cast)
+    }
+}
+
+impl FileControlParagraph {
+    pub fn select_entries(&self) -> impl Iterator<Item = SelectEntry> + '_ {
+        self.0.children().filter_map(SelectEntry::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Organization {
+    Sequential,
+    LineSequential,
+    Indexed,
+    Relative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    Sequential,
+    Random,
+    Dynamic,
+}
+
+impl SelectEntry {
+    fn clause(&self, kind: SyntaxKind) -> Option<SyntaxNode> {
+        self.0.children().find(|n| n.kind() == kind)
+    }
+
+    fn clause_name(&self, kind: SyntaxKind) -> Option<String> {
+        child_token(&self.clause(kind)?, IDENT).map(|t| t.text().to_string())
+    }
+
+    pub fn file_name(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    pub fn is_optional(&self) -> bool {
+        child_token(&self.0, OPTIONAL_KW).is_some()
+    }
+
+    /// The external name the file is assigned to, a data name or the value
+    /// of a literal.
+    pub fn assign_to(&self) -> Option<String> {
+        let clause = self.clause(ASSIGN_CLAUSE)?;
+        match clause.children().find_map(Literal::cast) {
+            Some(literal) => literal.string_value(),
+            None => child_token(&clause, IDENT).map(|t| t.text().to_string()),
+        }
+    }
+
+    /// The file organization, SEQUENTIAL when not given.
+    pub fn organization(&self) -> Organization {
+        let Some(clause) = self.clause(ORGANIZATION_CLAUSE) else {
+            return Organization::Sequential;
+        };
+        if child_token(&clause, LINE_KW).is_some() {
+            Organization::LineSequential
+        } else if child_token(&clause, INDEXED_KW).is_some() {
+            Organization::Indexed
+        } else if child_token(&clause, RELATIVE_KW).is_some() {
+            Organization::Relative
+        } else {
+            Organization::Sequential
+        }
+    }
+
+    /// The access mode, SEQUENTIAL when not given.
+    pub fn access_mode(&self) -> AccessMode {
+        let Some(clause) = self.clause(ACCESS_MODE_CLAUSE) else {
+            return AccessMode::Sequential;
+        };
+        if child_token(&clause, RANDOM_KW).is_some() {
+            AccessMode::Random
+        } else if child_token(&clause, DYNAMIC_KW).is_some() {
+            AccessMode::Dynamic
+        } else {
+            AccessMode::Sequential
+        }
+    }
+
+    pub fn record_key(&self) -> Option<String> {
+        self.clause_name(RECORD_KEY_CLAUSE)
+    }
+
+    /// Alternate record keys, with whether duplicates are allowed.
+    pub fn alternate_keys(&self) -> Vec<(String, bool)> {
+        self.0
+            .children()
+            .filter(|n| n.kind() == ALTERNATE_KEY_CLAUSE)
+            .filter_map(|n| {
+                let name = child_token(&n, IDENT)?.text().to_string();
+                Some((name, child_token(&n, DUPLICATES_KW).is_some()))
+            })
+            .collect()
+    }
+
+    pub fn relative_key(&self) -> Option<String> {
+        self.clause_name(RELATIVE_KEY_CLAUSE)
+    }
+
+    pub fn file_status(&self) -> Option<String> {
+        self.clause_name(FILE_STATUS_CLAUSE)
+    }
+}
+
+impl DataDivision {
+    pub fn file_section(&self) -> Option<FileSection> {
+        self.0.children().find_map(FileSection::cast)
+    }
+
+    pub fn working_storage_section(&self) -> Option<WorkingStorageSection> {
+        self.0.children().find_map(WorkingStorageSection::cast)
+    }
+
+    pub fn local_storage_section(&self) -> Option<LocalStorageSection> {
+        self.0.children().find_map(LocalStorageSection::cast)
+    }
+
+    pub fn linkage_section(&self) -> Option<LinkageSection> {
+        self.0.children().find_map(LinkageSection::cast)
+    }
+
+    /// The item `data_ref` refers to: the one with its name whose
+    /// enclosing groups, or file, include its qualifiers in order. It is an
+    /// error when several items match.
+    pub fn resolve(&self, data_ref: &DataRef) -> Result<DataItem, ResolveError> {
+        let name = data_ref.name().unwrap_or_default();
+        let qualifiers = data_ref.qualifiers();
+        let mut matches = self
+            .0
+            .descendants()
+            .filter_map(DataItem::cast)
+            .filter(|item| item.name().is_some_and(|n| n.eq_ignore_ascii_case(&name)))
+            .filter(|item| {
+                let mut enclosing = item.syntax().ancestors().skip(1).filter_map(|node| match node.kind() {
+                    DATA_ITEM => DataItem(node).name(),
+                    FILE_DESCRIPTION => FileDescription(node).name(),
+                    _ => None,
+                });
+                qualifiers.iter().all(|q| enclosing.any(|n| n.eq_ignore_ascii_case(q)))
+            });
+        match (matches.next(), matches.count()) {
+            (Some(item), 0) => Ok(item),
+            (Some(_), others) => Err(ResolveError::Ambiguous { name, candidates: others + 1 }),
+            (None, _) => Err(ResolveError::Undefined(name)),
+        }
+    }
+
+    /// The table whose OCCURS clause is INDEXED BY `index`.
+    pub fn indexed_table(&self, index: &str) -> Option<DataItem> {
+        self.0.descendants().filter_map(DataItem::cast).find(|item| is_index_of(item, index))
+    }
+}
+
+/// Why [`DataDivision::resolve`] found no single item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    Undefined(String),
+    /// Several items match the name and qualifiers given.
+    Ambiguous { name: String, candidates: usize },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::Undefined(name) => write!(f, "{} is not defined", name),
+            ResolveError::Ambiguous { name, candidates } => {
+                write!(f, "{} is ambiguous: {} items match, qualify it with OF or IN", name, candidates)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+impl FileSection {
+    pub fn file_descriptions(&self) -> impl Iterator<Item = FileDescription> + '_ {
+        self.0.children().filter_map(FileDescription::cast)
+    }
+}
+
+impl WorkingStorageSection {
+    /// Level 01, 66 and 77 entries.
+    pub fn items(&self) -> impl Iterator<Item = DataItem> + '_ {
+        self.0.children().filter_map(DataItem::cast)
+    }
+}
+
+impl LocalStorageSection {
+    /// Level 01, 66 and 77 entries.
+    pub fn items(&self) -> impl Iterator<Item = DataItem> + '_ {
+        self.0.children().filter_map(DataItem::cast)
+    }
+}
+
+impl LinkageSection {
+    /// Level 01, 66 and 77 entries.
+    pub fn items(&self) -> impl Iterator<Item = DataItem> + '_ {
+        self.0.children().filter_map(DataItem::cast)
+    }
+}
+
+impl FileDescription {
+    pub fn name(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    /// An SD entry, describing the work file of SORT or MERGE.
+    pub fn is_sort(&self) -> bool {
+        child_token(&self.0, SD_KW).is_some()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = DataItem> + '_ {
+        self.0.children().filter_map(DataItem::cast)
+    }
+}
+
+impl DataItem {
+    pub fn level(&self) -> Option<u8> {
+        child_token(&self.0, INTEGER_LITERAL)?.text().parse().ok()
+    }
+
+    /// The token following the level number, if it names the entry.
+    fn name_token(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .filter(|t| !t.kind().is_trivia())
+            .nth(1)
+            .filter(|t| matches!(t.kind(), IDENT | FILLER_KW))
+    }
+
+    /// The data name; `None` for FILLER and unnamed entries.
+    pub fn name(&self) -> Option<String> {
+        self.name_token()
+            .filter(|t| t.kind() == IDENT)
+            .map(|t| t.text().to_string())
+    }
+
+    pub fn is_filler(&self) -> bool {
+        self.name().is_none()
+    }
+
+    /// Entries subordinate to this one, level 88 condition names included.
+    pub fn children(&self) -> impl Iterator<Item = DataItem> + '_ {
+        self.0.children().filter_map(DataItem::cast)
+    }
+
+    pub fn is_group(&self) -> bool {
+        self.children().any(|child| child.level() != Some(88))
+    }
+
+    pub fn picture_clause(&self) -> Option<PictureClause> {
+        self.0.children().find_map(PictureClause::cast)
+    }
+
+    /// The analysed PICTURE, if the entry has a well-formed one.
+    pub fn picture(&self) -> Option<Picture> {
+        self.picture_clause()?.picture().ok()
+    }
+
+    pub fn usage_clause(&self) -> Option<UsageClause> {
+        self.0.children().find_map(UsageClause::cast)
+    }
+
+    /// The usage given on this entry itself. Items without one take the
+    /// usage of their group, or DISPLAY.
+    pub fn usage(&self) -> Option<Usage> {
+        self.usage_clause()?.usage()
+    }
+
+    pub fn value_clause(&self) -> Option<ValueClause> {
+        self.0.children().find_map(ValueClause::cast)
+    }
+
+    pub fn occurs_clause(&self) -> Option<OccursClause> {
+        self.0.children().find_map(OccursClause::cast)
+    }
+
+    /// Name of the item this entry redefines.
+    pub fn redefines(&self) -> Option<String> {
+        self.0.children().find_map(RedefinesClause::cast)?.name()
+    }
+
+    pub fn renames_clause(&self) -> Option<RenamesClause> {
+        self.0.children().find_map(RenamesClause::cast)
+    }
+
+    pub fn justified_clause(&self) -> Option<JustifiedClause> {
+        self.0.children().find_map(JustifiedClause::cast)
+    }
+
+    pub fn sign_clause(&self) -> Option<SignClause> {
+        self.0.children().find_map(SignClause::cast)
+    }
+
+    pub fn is_blank_when_zero(&self) -> bool {
+        self.0.children().any(|n| n.kind() == BLANK_WHEN_ZERO_CLAUSE)
+    }
+
+    pub fn synchronized_clause(&self) -> Option<SynchronizedClause> {
+        self.0.children().find_map(SynchronizedClause::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Usage {
+    Display,
+    /// BINARY, COMP and COMP-4
+    Binary,
+    /// COMP-1, single precision floating point
+    Float,
+    /// COMP-2, double precision floating point
+    Double,
+    /// COMP-3 and PACKED-DECIMAL
+    PackedDecimal,
+    /// COMP-5, binary limited by the storage size rather than the PICTURE
+    NativeBinary,
+    Pointer,
+    Index,
+}
+
+impl UsageClause {
+    pub fn usage(&self) -> Option<Usage> {
+        let usage = self
+            .0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .find_map(|t| {
+                Some(match t.kind() {
+                    DISPLAY_KW => Usage::Display,
+                    COMP_KW | BINARY_KW => Usage::Binary,
+                    COMP_1_KW => Usage::Float,
+                    COMP_2_KW => Usage::Double,
+                    COMP_3_KW | PACKED_DECIMAL_KW => Usage::PackedDecimal,
+                    COMP_5_KW => Usage::NativeBinary,
+                    POINTER_KW => Usage::Pointer,
+                    INDEX_KW => Usage::Index,
+                    _ => return None,
+                })
+            })?;
+        Some(usage)
+    }
+}
+
+/// One entry of a VALUE clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueEntry {
+    Single(Literal),
+    /// `literal THRU literal`
+    Range(Literal, Literal),
+}
+
+impl ValueClause {
+    pub fn entries(&self) -> Vec<ValueEntry> {
+        self.0
+            .children()
+            .filter_map(|node| match node.kind() {
+                LITERAL => Literal::cast(node).map(ValueEntry::Single),
+                VALUE_RANGE => {
+                    let mut bounds = node.children().filter_map(Literal::cast);
+                    Some(ValueEntry::Range(bounds.next()?, bounds.next()?))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The initial value of a data item, i.e. the first entry.
+    pub fn literal(&self) -> Option<Literal> {
+        self.0.children().find_map(Literal::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyOrder {
+    Ascending,
+    Descending,
+}
+
+impl OccursClause {
+    fn bounds(&self) -> Vec<u32> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .filter(|t| t.kind() == INTEGER_LITERAL)
+            .filter_map(|t| t.text().parse().ok())
+            .collect()
+    }
+
+    /// The minimum number of occurrences; equal to [`Self::max`] unless the
+    /// clause has the `min TO max` form.
+    pub fn min(&self) -> Option<u32> {
+        self.bounds().first().copied()
+    }
+
+    pub fn max(&self) -> Option<u32> {
+        self.bounds().last().copied()
+    }
+
+    pub fn depending_on(&self) -> Option<String> {
+        phrase_data_ref(&self.0, OCCURS_DEPENDING)?.name()
+    }
+
+    /// Key names in order, with their direction.
+    pub fn keys(&self) -> Vec<(KeyOrder, String)> {
+        let mut keys = Vec::new();
+        for phrase in self.0.children().filter(|n| n.kind() == OCCURS_KEY) {
+            let order = if child_token(&phrase, DESCENDING_KW).is_some() {
+                KeyOrder::Descending
+            } else {
+                KeyOrder::Ascending
+            };
+            keys.extend(phrase.children().filter_map(DataRef::cast).filter_map(|key| Some((order, key.name()?))));
+        }
+        keys
+    }
+
+    pub fn indexed_by(&self) -> Vec<String> {
+        self.0
+            .children()
+            .filter(|n| n.kind() == INDEXED_BY)
+            .flat_map(|n| idents(&n))
+            .collect()
+    }
+}
+
+fn idents(node: &SyntaxNode) -> Vec<String> {
+    node.children_with_tokens()
+        .filter_map(|el| el.into_token())
+        .filter(|t| t.kind() == IDENT)
+        .map(|t| t.text().to_string())
+        .collect()
+}
+
+impl RedefinesClause {
+    pub fn name(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+}
+
+impl RenamesClause {
+    pub fn from(&self) -> Option<String> {
+        idents(&self.0).into_iter().next()
+    }
+
+    /// The end of a `RENAMES a THRU b` range.
+    pub fn thru(&self) -> Option<String> {
+        idents(&self.0).into_iter().nth(1)
+    }
+}
+
+impl JustifiedClause {
+    pub fn is_right(&self) -> bool {
+        child_token(&self.0, RIGHT_KW).is_some()
+    }
+}
+
+impl SignClause {
+    pub fn is_leading(&self) -> bool {
+        child_token(&self.0, LEADING_KW).is_some()
+    }
+
+    pub fn is_separate(&self) -> bool {
+        child_token(&self.0, SEPARATE_KW).is_some()
+    }
+}
+
+impl SynchronizedClause {
+    pub fn is_left(&self) -> bool {
+        child_token(&self.0, LEFT_KW).is_some()
+    }
+}
+
+impl PictureClause {
+    pub fn picture_string(&self) -> Option<String> {
+        child_token(&self.0, PICTURE_STRING).map(|t| t.text().to_string())
+    }
+
+    /// The analysed PICTURE, with `.` and `,` swapped when the program's
+    /// SPECIAL-NAMES paragraph has `DECIMAL-POINT IS COMMA`.
+    pub fn picture(&self) -> Result<Picture, PictureError> {
+        Picture::parse_with_decimal_point(&self.picture_string().unwrap_or_default(), decimal_point_is_comma(&self.0))
+    }
+}
+
+impl IdentificationDivision {
+    pub fn program_id(&self) -> Option<ProgramIdClause> {
+        self.0.children().find_map(ProgramIdClause::cast)
+    }
+
+    pub fn paragraphs(&self) -> impl Iterator<Item = IdentificationParagraph> + '_ {
+        self.0.children().filter_map(IdentificationParagraph::cast)
+    }
+
+    fn paragraph_text(&self, kind: SyntaxKind) -> Option<String> {
+        self.paragraphs().find(|p| p.keyword() == Some(kind)).map(|p| p.text())
+    }
+
+    pub fn author(&self) -> Option<String> {
+        self.paragraph_text(AUTHOR_KW)
+    }
+
+    pub fn installation(&self) -> Option<String> {
+        self.paragraph_text(INSTALLATION_KW)
+    }
+
+    pub fn date_written(&self) -> Option<String> {
+        self.paragraph_text(DATE_WRITTEN_KW)
+    }
+
+    pub fn date_compiled(&self) -> Option<String> {
+        self.paragraph_text(DATE_COMPILED_KW)
+    }
+
+    pub fn security(&self) -> Option<String> {
+        self.paragraph_text(SECURITY_KW)
+    }
+
+    pub fn remarks(&self) -> Option<String> {
+        self.paragraph_text(REMARKS_KW)
+    }
+}
+
+impl IdentificationParagraph {
+    /// The paragraph name, e.g. `AUTHOR_KW`.
+    pub fn keyword(&self) -> Option<SyntaxKind> {
+        self.0.first_token().map(|t| t.kind())
+    }
+
+    /// The comment entries, one line each, joined by a space.
+    pub fn text(&self) -> String {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .filter(|t| t.kind() == COMMENT_ENTRY)
+            .map(|t| t.text().to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl ProgramIdClause {
+    /// The program name, from a word or an alphanumeric literal.
+    pub fn name(&self) -> Option<String> {
+        if let Some(literal) = self.0.children().find_map(Literal::cast) {
+            return literal.string_value();
+        }
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    pub fn is_initial(&self) -> bool {
+        child_token(&self.0, INITIAL_KW).is_some()
+    }
+
+    pub fn is_common(&self) -> bool {
+        child_token(&self.0, COMMON_KW).is_some()
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        child_token(&self.0, RECURSIVE_KW).is_some()
+    }
+}
+
+impl ProcedureDivision {
+    pub fn display_statements(&self) -> impl Iterator<Item = DisplayStmt> + '_ {
+        self.0.descendants().filter_map(DisplayStmt::cast)
+    }
+
+    pub fn call_statements(&self) -> impl Iterator<Item = CallStmt> + '_ {
+        self.0.descendants().filter_map(CallStmt::cast)
+    }
+
+    /// Parameters of the `USING` phrase of the header.
+    pub fn using(&self) -> Vec<Argument> {
+        using_arguments(&self.0)
+    }
+
+    /// Item of the `RETURNING` phrase of the header.
+    pub fn returning(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, RETURNING_PHRASE)
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = Section> + '_ {
+        self.0.children().filter_map(Section::cast)
+    }
+
+    /// All paragraphs, whether inside a section or not.
+    pub fn paragraphs(&self) -> impl Iterator<Item = Paragraph> + '_ {
+        self.0.descendants().filter_map(Paragraph::cast)
+    }
+
+    /// Sentences before the first paragraph or section.
+    pub fn sentences(&self) -> impl Iterator<Item = Sentence> + '_ {
+        self.0.children().filter_map(Sentence::cast)
+    }
+
+    pub fn section(&self, name: &str) -> Option<Section> {
+        self.sections().find(|s| s.name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    pub fn paragraph(&self, name: &str) -> Option<Paragraph> {
+        self.paragraphs().find(|p| p.name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+}
+
+/// Name of a section or paragraph, which may be all digits.
+fn procedure_name(node: &SyntaxNode) -> Option<String> {
+    node.children_with_tokens()
+        .filter_map(|el| el.into_token())
+        .find(|t| matches!(t.kind(), IDENT | INTEGER_LITERAL))
+        .map(|t| t.text().to_string())
+}
+
+impl Section {
+    pub fn name(&self) -> Option<String> {
+        procedure_name(&self.0)
+    }
+
+    pub fn paragraphs(&self) -> impl Iterator<Item = Paragraph> + '_ {
+        self.0.children().filter_map(Paragraph::cast)
+    }
+
+    /// Sentences before the first paragraph of the section.
+    pub fn sentences(&self) -> impl Iterator<Item = Sentence> + '_ {
+        self.0.children().filter_map(Sentence::cast)
+    }
+}
+
+impl Paragraph {
+    pub fn name(&self) -> Option<String> {
+        procedure_name(&self.0)
+    }
+
+    /// The section the paragraph belongs to, if any.
+    pub fn section(&self) -> Option<Section> {
+        self.0.parent().and_then(Section::cast)
+    }
+
+    pub fn sentences(&self) -> impl Iterator<Item = Sentence> + '_ {
+        self.0.children().filter_map(Sentence::cast)
+    }
+
+    pub fn statements(&self) -> impl Iterator<Item = Stmt> + '_ {
+        self.sentences().flat_map(|s| s.statements().collect::<Vec<_>>())
+    }
+}
+
+impl Sentence {
+    pub fn statements(&self) -> impl Iterator<Item = Stmt> + '_ {
+        self.0.children().filter_map(Stmt::cast)
+    }
+}
+
+/// A value used by a statement: a literal or a data reference.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Operand(SyntaxNode);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OperandKind {
+    Literal(Literal),
+    DataRef(DataRef),
+    FunctionCall(FunctionCall),
+}
+
+impl Operand {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if matches!(node.kind(), LITERAL | DATA_REF | FUNCTION_CALL) {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn syntax(&self) -> &SyntaxNode {
+        &self.0
+    }
+
+    pub fn kind(&self) -> OperandKind {
+        let node = self.0.clone();
+        match node.kind() {
+            LITERAL => OperandKind::Literal(Literal(node)),
+            DATA_REF => OperandKind::DataRef(DataRef(node)),
+            FUNCTION_CALL => OperandKind::FunctionCall(FunctionCall(node)),
+            kind => unreachable!("{:?} is not an operand", kind),
+        }
+    }
+
+    pub fn as_data_ref(&self) -> Option<DataRef> {
+        DataRef::cast(self.0.clone())
+    }
+
+    pub fn as_literal(&self) -> Option<Literal> {
+        Literal::cast(self.0.clone())
+    }
+}
+
+impl DataRef {
+    pub fn name(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    /// The names after OF or IN, from the innermost group outwards.
+    pub fn qualifiers(&self) -> Vec<String> {
+        idents(&self.0).into_iter().skip(1).collect()
+    }
+
+    pub fn subscripts(&self) -> Vec<Subscript> {
+        let Some(subscripts) = self.0.children().find(|n| n.kind() == SUBSCRIPTS) else {
+            return Vec::new();
+        };
+        subscripts
+            .children_with_tokens()
+            .filter_map(|el| match el {
+                rowan::NodeOrToken::Token(t) if t.kind() == ALL_KW => Some(Subscript::All),
+                rowan::NodeOrToken::Token(_) => None,
+                rowan::NodeOrToken::Node(n) => Expr::cast(n).map(Subscript::Expr),
+            })
+            .collect()
+    }
+
+    pub fn ref_mod(&self) -> Option<RefMod> {
+        self.0.children().find_map(RefMod::cast)
+    }
+}
+
+impl FunctionCall {
+    /// The function name as written.
+    pub fn name(&self) -> Option<String> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .filter(|t| !t.kind().is_trivia())
+            .nth(1)
             .map(|t| t.text().to_string())
     }
-}
 
-impl ProcedureDivision {
-    pub fn display_statements(&self) -> impl Iterator<Item = DisplayStmt> + '_ {
-        self.0.children().filter_map(DisplayStmt::cast)
+    /// The catalogue entry of the function, unless it is not a standard one.
+    pub fn intrinsic(&self) -> Option<&'static intrinsics::IntrinsicFunction> {
+        intrinsics::lookup(&self.name()?)
+    }
+
+    /// The argument expressions; the LEADING or TRAILING keyword of TRIM is
+    /// not among them.
+    pub fn arguments(&self) -> Vec<Expr> {
+        self.0
+            .children()
+            .find(|n| n.kind() == FUNCTION_ARGS)
+            .map(|n| n.children().filter_map(Expr::cast).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn ref_mod(&self) -> Option<RefMod> {
+        self.0.children().find_map(RefMod::cast)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Subscript {
+    /// `ALL`, every occurrence, as in a table argument of an intrinsic
+    /// function.
+    All,
+    /// An occurrence number, index or arithmetic expression.
+    Expr(Expr),
+}
+
+impl Subscript {
+    /// A relative subscript `name + n` or `name - n`, as the name and the
+    /// signed offset.
+    pub fn relative(&self) -> Option<(DataRef, i128)> {
+        let Subscript::Expr(expr) = self else { return None };
+        let ExprKind::Binary(bin) = expr.kind() else { return None };
+        let sign = match bin.op()? {
+            BinaryOp::Add => 1,
+            BinaryOp::Subtract => -1,
+            _ => return None,
+        };
+        let ExprKind::DataRef(name) = bin.lhs()?.kind() else { return None };
+        let ExprKind::Literal(offset) = bin.rhs()?.kind() else { return None };
+        let offset = offset.numeric_value().filter(|n| n.scale == 0)?;
+        Some((name, sign * offset.value))
+    }
+}
+
+impl RefMod {
+    /// Leftmost character position, starting at 1.
+    pub fn start(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+
+    /// Number of characters, to the end of the item when absent.
+    pub fn length(&self) -> Option<Expr> {
+        self.0
+            .children_with_tokens()
+            .skip_while(|el| el.kind() != COLON)
+            .filter_map(|el| el.into_node())
+            .find_map(Expr::cast)
+    }
+}
+
+impl MoveStmt {
+    pub fn is_corresponding(&self) -> bool {
+        child_token(&self.0, CORRESPONDING_KW).is_some()
+    }
+
+    pub fn source(&self) -> Option<Operand> {
+        self.0.children().find_map(Operand::cast)
+    }
+
+    pub fn targets(&self) -> Vec<DataRef> {
+        self.0
+            .children_with_tokens()
+            .skip_while(|el| el.kind() != TO_KW)
+            .filter_map(|el| el.into_node())
+            .filter_map(DataRef::cast)
+            .collect()
+    }
+}
+
+/// An ADD, SUBTRACT, MULTIPLY or DIVIDE statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ArithmeticStmt(SyntaxNode);
+
+impl ArithmeticStmt {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if matches!(node.kind(), ADD_STMT | SUBTRACT_STMT | MULTIPLY_STMT | DIVIDE_STMT) {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn syntax(&self) -> &SyntaxNode {
+        &self.0
+    }
+
+    pub fn is_corresponding(&self) -> bool {
+        child_token(&self.0, CORRESPONDING_KW).is_some()
+    }
+
+    fn phrase(&self, kind: SyntaxKind) -> Option<SyntaxNode> {
+        self.0.children().find(|n| n.kind() == kind)
+    }
+
+    /// The values operated on: the items before TO, FROM, BY or INTO, and
+    /// with GIVING the items after it as well.
+    pub fn operands(&self) -> Vec<Operand> {
+        let giving = self.phrase(GIVING_PHRASE).is_some();
+        self.0
+            .children()
+            .filter_map(|n| match n.kind() {
+                RECEIVER if giving => n.children().find_map(Operand::cast),
+                _ => Operand::cast(n),
+            })
+            .collect()
+    }
+
+    /// The items that receive the result.
+    pub fn receivers(&self) -> Vec<Receiver> {
+        match self.phrase(GIVING_PHRASE) {
+            Some(giving) => giving.children().filter_map(Receiver::cast).collect(),
+            None => self.0.children().filter_map(Receiver::cast).collect(),
+        }
+    }
+
+    pub fn remainder(&self) -> Option<Receiver> {
+        self.phrase(REMAINDER_PHRASE)?.children().find_map(Receiver::cast)
+    }
+
+    pub fn on_size_error(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, ON_SIZE_ERROR)
+    }
+
+    pub fn not_on_size_error(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_ON_SIZE_ERROR)
+    }
+}
+
+/// Statements of the `kind` phrase of `node`.
+fn phrase_statements(node: &SyntaxNode, kind: SyntaxKind) -> Vec<Stmt> {
+    node.children()
+        .find(|n| n.kind() == kind)
+        .map(|phrase| phrase.children().filter_map(Stmt::cast).collect())
+        .unwrap_or_default()
+}
+
+impl ComputeStmt {
+    pub fn receivers(&self) -> Vec<Receiver> {
+        self.0.children().filter_map(Receiver::cast).collect()
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+
+    pub fn on_size_error(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, ON_SIZE_ERROR)
+    }
+
+    pub fn not_on_size_error(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_ON_SIZE_ERROR)
+    }
+}
+
+impl Receiver {
+    pub fn data_ref(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    pub fn is_rounded(&self) -> bool {
+        child_token(&self.0, ROUNDED_KW).is_some()
+    }
+
+    /// The rounding mode name, e.g. `NEAREST-EVEN`.
+    pub fn rounded_mode(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+}
+
+/// An arithmetic expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Expr(SyntaxNode);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExprKind {
+    Binary(BinExpr),
+    Prefix(PrefixExpr),
+    Paren(ParenExpr),
+    Literal(Literal),
+    DataRef(DataRef),
+    FunctionCall(FunctionCall),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+impl Expr {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if matches!(node.kind(), BIN_EXPR | PREFIX_EXPR | PAREN_EXPR | LITERAL | DATA_REF | FUNCTION_CALL) {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn syntax(&self) -> &SyntaxNode {
+        &self.0
+    }
+
+    pub fn kind(&self) -> ExprKind {
+        let node = self.0.clone();
+        match node.kind() {
+            BIN_EXPR => ExprKind::Binary(BinExpr(node)),
+            PREFIX_EXPR => ExprKind::Prefix(PrefixExpr(node)),
+            PAREN_EXPR => ExprKind::Paren(ParenExpr(node)),
+            LITERAL => ExprKind::Literal(Literal(node)),
+            DATA_REF => ExprKind::DataRef(DataRef(node)),
+            FUNCTION_CALL => ExprKind::FunctionCall(FunctionCall(node)),
+            kind => unreachable!("{:?} is not an expression", kind),
+        }
+    }
+}
+
+impl BinExpr {
+    pub fn lhs(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+
+    pub fn rhs(&self) -> Option<Expr> {
+        self.0.children().filter_map(Expr::cast).nth(1)
+    }
+
+    pub fn op(&self) -> Option<BinaryOp> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .find_map(|t| match t.kind() {
+                PLUS => Some(BinaryOp::Add),
+                MINUS => Some(BinaryOp::Subtract),
+                STAR => Some(BinaryOp::Multiply),
+                SLASH => Some(BinaryOp::Divide),
+                POWER => Some(BinaryOp::Power),
+                _ => None,
+            })
+    }
+}
+
+impl PrefixExpr {
+    pub fn is_negation(&self) -> bool {
+        child_token(&self.0, MINUS).is_some()
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+}
+
+impl ParenExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+}
+
+impl IfStmt {
+    pub fn condition(&self) -> Option<Condition> {
+        self.0.children().find_map(Condition::cast)
+    }
+
+    pub fn then_statements(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, THEN_BRANCH)
+    }
+
+    pub fn else_statements(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, ELSE_BRANCH)
+    }
+
+    pub fn has_else(&self) -> bool {
+        self.0.children().any(|n| n.kind() == ELSE_BRANCH)
+    }
+}
+
+/// A conditional expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Condition(SyntaxNode);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConditionKind {
+    Relation(RelationCondition),
+    Class(ClassCondition),
+    Sign(SignCondition),
+    ConditionName(ConditionName),
+    Not(NotCondition),
+    Combined(CombinedCondition),
+    Paren(ParenCondition),
+}
+
+impl Condition {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if matches!(
+            node.kind(),
+            RELATION_CONDITION
+                | CLASS_CONDITION
+                | SIGN_CONDITION
+                | CONDITION_NAME
+                | NOT_CONDITION
+                | COMBINED_CONDITION
+                | PAREN_CONDITION
+        ) {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn syntax(&self) -> &SyntaxNode {
+        &self.0
+    }
+
+    pub fn kind(&self) -> ConditionKind {
+        let node = self.0.clone();
+        match node.kind() {
+            RELATION_CONDITION => ConditionKind::Relation(RelationCondition(node)),
+            CLASS_CONDITION => ConditionKind::Class(ClassCondition(node)),
+            SIGN_CONDITION => ConditionKind::Sign(SignCondition(node)),
+            CONDITION_NAME => ConditionKind::ConditionName(ConditionName(node)),
+            NOT_CONDITION => ConditionKind::Not(NotCondition(node)),
+            COMBINED_CONDITION => ConditionKind::Combined(CombinedCondition(node)),
+            PAREN_CONDITION => ConditionKind::Paren(ParenCondition(node)),
+            kind => unreachable!("{:?} is not a condition", kind),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Equal,
+    Greater,
+    Less,
+    GreaterOrEqual,
+    LessOrEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RelationalOp {
+    pub comparison: Comparison,
+    /// Preceded by NOT
+    pub negated: bool,
+}
+
+impl RelationalOp {
+    fn from_node(node: &SyntaxNode) -> Option<Self> {
+        let tokens: Vec<_> = node
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .map(|t| t.kind())
+            .collect();
+        let or_equal = tokens.contains(&EQUAL_KW);
+        let comparison = tokens.iter().find_map(|kind| match kind {
+            GREATER_KW if or_equal => Some(Comparison::GreaterOrEqual),
+            LESS_KW if or_equal => Some(Comparison::LessOrEqual),
+            GREATER_KW | GREATER => Some(Comparison::Greater),
+            LESS_KW | LESS => Some(Comparison::Less),
+            EQUAL_KW | EQUALS => Some(Comparison::Equal),
+            GREATER_EQUALS => Some(Comparison::GreaterOrEqual),
+            LESS_EQUALS => Some(Comparison::LessOrEqual),
+            _ => None,
+        })?;
+        Some(RelationalOp { comparison, negated: tokens.contains(&NOT_KW) })
+    }
+}
+
+impl RelationCondition {
+    fn rel_op_node(&self) -> Option<SyntaxNode> {
+        self.0.children().find(|n| n.kind() == REL_OP)
+    }
+
+    /// The subject as written; `None` in an abbreviated relation.
+    pub fn subject(&self) -> Option<Expr> {
+        self.0
+            .children()
+            .take_while(|n| n.kind() != REL_OP)
+            .find_map(Expr::cast)
+            .filter(|_| self.rel_op_node().is_some())
+    }
+
+    /// The operator as written; `None` when only the object is given.
+    pub fn op(&self) -> Option<RelationalOp> {
+        RelationalOp::from_node(&self.rel_op_node()?)
+    }
+
+    pub fn object(&self) -> Option<Expr> {
+        let after_op = self.rel_op_node().is_some();
+        self.0
+            .children()
+            .skip_while(|n| after_op && n.kind() != REL_OP)
+            .find_map(Expr::cast)
+    }
+
+    pub fn is_abbreviated(&self) -> bool {
+        self.subject().is_none() || self.op().is_none()
+    }
+
+    /// Relation conditions preceding this one in the same condition, the
+    /// closest first.
+    fn preceding_relations(&self) -> Vec<RelationCondition> {
+        let mut top = self.0.clone();
+        while let Some(parent) = top.parent().filter(|p| Condition::cast(p.clone()).is_some()) {
+            top = parent;
+        }
+        let mut relations: Vec<_> = top
+            .descendants()
+            .filter_map(RelationCondition::cast)
+            .take_while(|r| r != self)
+            .collect();
+        relations.reverse();
+        relations
+    }
+
+    /// The subject, taken from the preceding relation if it is omitted.
+    pub fn effective_subject(&self) -> Option<Expr> {
+        self.subject()
+            .or_else(|| self.preceding_relations().iter().find_map(|r| r.subject()))
+    }
+
+    /// The operator, taken from the preceding relation if it is omitted.
+    pub fn effective_op(&self) -> Option<RelationalOp> {
+        self.op()
+            .or_else(|| self.preceding_relations().iter().find_map(|r| r.op()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClassName {
+    Numeric,
+    Alphabetic,
+    AlphabeticLower,
+    AlphabeticUpper,
+}
+
+impl ClassCondition {
+    pub fn subject(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+
+    pub fn class(&self) -> Option<ClassName> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .find_map(|t| match t.kind() {
+                NUMERIC_KW => Some(ClassName::Numeric),
+                ALPHABETIC_KW => Some(ClassName::Alphabetic),
+                ALPHABETIC_LOWER_KW => Some(ClassName::AlphabeticLower),
+                ALPHABETIC_UPPER_KW => Some(ClassName::AlphabeticUpper),
+                _ => None,
+            })
+    }
+
+    pub fn is_negated(&self) -> bool {
+        child_token(&self.0, NOT_KW).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sign {
+    Positive,
+    Negative,
+    Zero,
+}
+
+impl SignCondition {
+    pub fn subject(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+
+    pub fn sign(&self) -> Option<Sign> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .find_map(|t| match t.kind() {
+                POSITIVE_KW => Some(Sign::Positive),
+                NEGATIVE_KW => Some(Sign::Negative),
+                ZERO_KW => Some(Sign::Zero),
+                _ => None,
+            })
+    }
+
+    pub fn is_negated(&self) -> bool {
+        child_token(&self.0, NOT_KW).is_some()
+    }
+}
+
+impl ConditionName {
+    /// The level 88 item tested.
+    pub fn data_ref(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+}
+
+impl NotCondition {
+    pub fn condition(&self) -> Option<Condition> {
+        self.0.children().find_map(Condition::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogicalOp {
+    And,
+    Or,
+}
+
+impl CombinedCondition {
+    pub fn lhs(&self) -> Option<Condition> {
+        self.0.children().find_map(Condition::cast)
+    }
+
+    pub fn rhs(&self) -> Option<Condition> {
+        self.0.children().filter_map(Condition::cast).nth(1)
+    }
+
+    pub fn op(&self) -> Option<LogicalOp> {
+        if child_token(&self.0, AND_KW).is_some() {
+            Some(LogicalOp::And)
+        } else if child_token(&self.0, OR_KW).is_some() {
+            Some(LogicalOp::Or)
+        } else {
+            None
+        }
+    }
+}
+
+impl ParenCondition {
+    pub fn condition(&self) -> Option<Condition> {
+        self.0.children().find_map(Condition::cast)
+    }
+}
+
+impl EvaluateStmt {
+    pub fn subjects(&self) -> Vec<EvaluateSubject> {
+        self.0.children().filter_map(EvaluateSubject::cast).collect()
+    }
+
+    /// The WHEN branches in order, WHEN OTHER excluded.
+    pub fn branches(&self) -> Vec<WhenBranch> {
+        self.0.children().filter_map(WhenBranch::cast).collect()
+    }
+
+    pub fn has_when_other(&self) -> bool {
+        self.0.children().any(|n| n.kind() == WHEN_OTHER)
+    }
+
+    pub fn when_other_statements(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, WHEN_OTHER)
+    }
+}
+
+/// What an EVALUATE subject or WHEN object selects on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Selection {
+    Any,
+    True,
+    False,
+    Condition(Condition),
+    /// `[NOT] value [THRU value]`; subjects never have NOT or THRU.
+    Value {
+        negated: bool,
+        value: Expr,
+        thru: Option<Expr>,
+    },
+}
+
+fn selection(node: &SyntaxNode) -> Option<Selection> {
+    if child_token(node, ANY_KW).is_some() {
+        return Some(Selection::Any);
+    }
+    if child_token(node, TRUE_KW).is_some() {
+        return Some(Selection::True);
+    }
+    if child_token(node, FALSE_KW).is_some() {
+        return Some(Selection::False);
+    }
+    if let Some(condition) = node.children().find_map(Condition::cast) {
+        return Some(Selection::Condition(condition));
+    }
+    let mut values = node.children().filter_map(Expr::cast);
+    Some(Selection::Value {
+        negated: child_token(node, NOT_KW).is_some(),
+        value: values.next()?,
+        thru: values.next(),
+    })
+}
+
+impl EvaluateSubject {
+    pub fn selection(&self) -> Option<Selection> {
+        selection(&self.0)
+    }
+}
+
+impl WhenObject {
+    pub fn selection(&self) -> Option<Selection> {
+        selection(&self.0)
+    }
+}
+
+impl WhenBranch {
+    /// The object lists of the WHEN phrases; the branch is taken when any
+    /// of them matches the subjects.
+    pub fn alternatives(&self) -> Vec<Vec<WhenObject>> {
+        self.0
+            .children()
+            .filter(|n| n.kind() == WHEN_PHRASE)
+            .map(|phrase| phrase.children().filter_map(WhenObject::cast).collect())
+            .collect()
+    }
+
+    pub fn statements(&self) -> Vec<Stmt> {
+        self.0.children().filter_map(Stmt::cast).collect()
+    }
+}
+
+impl PerformStmt {
+    /// Whether the statements to perform are written inside the PERFORM,
+    /// rather than named by a procedure.
+    pub fn is_inline(&self) -> bool {
+        self.procedure().is_none()
+    }
+
+    pub fn procedure(&self) -> Option<ProcedureRange> {
+        self.0.children().find_map(ProcedureRange::cast)
+    }
+
+    /// The repeat count of `n TIMES`.
+    pub fn times(&self) -> Option<Operand> {
+        self.0
+            .children()
+            .find(|n| n.kind() == TIMES_PHRASE)?
+            .children()
+            .find_map(Operand::cast)
+    }
+
+    /// Whether the condition is tested after each iteration, i.e.
+    /// `WITH TEST AFTER`.
+    pub fn is_test_after(&self) -> bool {
+        self.0
+            .children()
+            .find(|n| n.kind() == TEST_PHRASE)
+            .is_some_and(|phrase| child_token(&phrase, AFTER_KW).is_some())
+    }
+
+    /// The condition of a plain `UNTIL` loop.
+    pub fn until(&self) -> Option<Condition> {
+        self.0
+            .children()
+            .find(|n| n.kind() == UNTIL_PHRASE)?
+            .children()
+            .find_map(Condition::cast)
+    }
+
+    /// The VARYING phrase followed by any AFTER phrases, outermost loop first.
+    pub fn varying(&self) -> Vec<VaryingPhrase> {
+        self.0.children().filter_map(VaryingPhrase::cast).collect()
+    }
+
+    /// The statements of an inline PERFORM.
+    pub fn statements(&self) -> Vec<Stmt> {
+        self.0.children().filter_map(Stmt::cast).collect()
+    }
+}
+
+impl ProcedureRange {
+    pub fn start(&self) -> Option<String> {
+        idents(&self.0).into_iter().next()
+    }
+
+    /// The last procedure of a THRU range.
+    pub fn end(&self) -> Option<String> {
+        idents(&self.0).into_iter().nth(1)
+    }
+}
+
+impl VaryingPhrase {
+    pub fn counter(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    fn operand_after(&self, keyword: SyntaxKind) -> Option<Operand> {
+        self.0
+            .children_with_tokens()
+            .skip_while(|el| el.kind() != keyword)
+            .filter_map(|el| el.into_node())
+            .find_map(Operand::cast)
+    }
+
+    pub fn from(&self) -> Option<Operand> {
+        self.operand_after(FROM_KW)
+    }
+
+    pub fn by(&self) -> Option<Operand> {
+        self.operand_after(BY_KW)
+    }
+
+    pub fn until(&self) -> Option<Condition> {
+        self.0.children().find_map(Condition::cast)
+    }
+}
+
+impl GoToStmt {
+    /// The procedures jumped to; several with DEPENDING ON, none when the
+    /// target is set by ALTER.
+    pub fn targets(&self) -> Vec<String> {
+        idents(&self.0)
+    }
+
+    /// The identifier whose value selects the target.
+    pub fn depending_on(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitKind {
+    /// A plain EXIT, which does nothing
+    Plain,
+    Program,
+    Paragraph,
+    Section,
+    Perform,
+    PerformCycle,
+}
+
+impl ExitStmt {
+    pub fn kind(&self) -> ExitKind {
+        if child_token(&self.0, PROGRAM_KW).is_some() {
+            ExitKind::Program
+        } else if child_token(&self.0, PARAGRAPH_KW).is_some() {
+            ExitKind::Paragraph
+        } else if child_token(&self.0, SECTION_KW).is_some() {
+            ExitKind::Section
+        } else if child_token(&self.0, CYCLE_KW).is_some() {
+            ExitKind::PerformCycle
+        } else if child_token(&self.0, PERFORM_KW).is_some() {
+            ExitKind::Perform
+        } else {
+            ExitKind::Plain
+        }
+    }
+}
+
+impl StopStmt {
+    /// `STOP RUN`, as opposed to `STOP literal` which only pauses.
+    pub fn is_run(&self) -> bool {
+        child_token(&self.0, RUN_KW).is_some()
+    }
+
+    pub fn literal(&self) -> Option<Literal> {
+        self.0.children().find_map(Literal::cast)
+    }
+}
+
+impl AlterStmt {
+    /// `(altered paragraph, new GO TO target)` pairs.
+    pub fn alterations(&self) -> Vec<(String, String)> {
+        idents(&self.0)
+            .chunks(2)
+            .filter_map(|pair| Some((pair[0].clone(), pair.get(1)?.clone())))
+            .collect()
+    }
+}
+
+/// The data reference inside the `kind` phrase of `node`.
+fn phrase_data_ref(node: &SyntaxNode, kind: SyntaxKind) -> Option<DataRef> {
+    node.children()
+        .find(|n| n.kind() == kind)?
+        .children()
+        .find_map(DataRef::cast)
+}
+
+impl StringStmt {
+    pub fn sources(&self) -> Vec<StringSource> {
+        self.0.children().filter_map(StringSource::cast).collect()
+    }
+
+    pub fn target(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, INTO_PHRASE)
+    }
+
+    pub fn pointer(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, POINTER_PHRASE)
+    }
+
+    pub fn on_overflow(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, ON_OVERFLOW)
+    }
+
+    pub fn not_on_overflow(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_ON_OVERFLOW)
+    }
+}
+
+impl StringSource {
+    /// The sending items, all but the delimiter.
+    pub fn values(&self) -> Vec<Operand> {
+        let mut operands: Vec<_> = self.0.children().filter_map(Operand::cast).collect();
+        if !self.is_delimited_by_size() {
+            operands.pop();
+        }
+        operands
+    }
+
+    /// `DELIMITED BY SIZE`: the whole of each item is sent.
+    pub fn is_delimited_by_size(&self) -> bool {
+        child_token(&self.0, SIZE_KW).is_some()
+    }
+
+    pub fn delimiter(&self) -> Option<Operand> {
+        if self.is_delimited_by_size() {
+            return None;
+        }
+        self.0.children().filter_map(Operand::cast).last()
+    }
+}
+
+impl UnstringStmt {
+    pub fn source(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    pub fn delimiters(&self) -> Vec<UnstringDelimiter> {
+        self.0
+            .children()
+            .find(|n| n.kind() == DELIMITED_PHRASE)
+            .map(|phrase| phrase.children().filter_map(UnstringDelimiter::cast).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn receivers(&self) -> Vec<UnstringReceiver> {
+        self.0.children().filter_map(UnstringReceiver::cast).collect()
+    }
+
+    pub fn pointer(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, POINTER_PHRASE)
+    }
+
+    /// The field counting the receivers acted upon.
+    pub fn tallying(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, TALLYING_PHRASE)
+    }
+
+    pub fn on_overflow(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, ON_OVERFLOW)
+    }
+
+    pub fn not_on_overflow(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_ON_OVERFLOW)
+    }
+}
+
+impl UnstringDelimiter {
+    /// `ALL`: adjacent occurrences count as one delimiter.
+    pub fn is_all(&self) -> bool {
+        child_token(&self.0, ALL_KW).is_some()
+    }
+
+    pub fn value(&self) -> Option<Operand> {
+        self.0.children().find_map(Operand::cast)
+    }
+}
+
+impl UnstringReceiver {
+    fn data_ref_after(&self, keyword: SyntaxKind) -> Option<DataRef> {
+        self.0
+            .children_with_tokens()
+            .skip_while(|el| el.kind() != keyword)
+            .filter_map(|el| el.into_node())
+            .find_map(DataRef::cast)
+    }
+
+    pub fn target(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    /// Receives the delimiter that ended this field.
+    pub fn delimiter_in(&self) -> Option<DataRef> {
+        self.data_ref_after(DELIMITER_KW)
+    }
+
+    /// Receives the number of characters moved into this field.
+    pub fn count_in(&self) -> Option<DataRef> {
+        self.data_ref_after(COUNT_KW)
+    }
+}
+
+impl InspectStmt {
+    pub fn target(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    pub fn tallying(&self) -> Vec<TallyingClause> {
+        self.0.children().filter_map(TallyingClause::cast).collect()
+    }
+
+    pub fn replacing(&self) -> Vec<InspectGroup> {
+        self.0
+            .children()
+            .find(|n| n.kind() == REPLACING_PHRASE)
+            .map(|phrase| phrase.children().filter_map(InspectGroup::cast).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn converting(&self) -> Option<ConvertingPhrase> {
+        self.0.children().find_map(ConvertingPhrase::cast)
+    }
+}
+
+impl TallyingClause {
+    pub fn counter(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    pub fn groups(&self) -> Vec<InspectGroup> {
+        self.0.children().filter_map(InspectGroup::cast).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InspectMode {
+    Characters,
+    All,
+    Leading,
+    First,
+}
+
+impl InspectGroup {
+    pub fn mode(&self) -> Option<InspectMode> {
+        match self.0.first_token()?.kind() {
+            CHARACTERS_KW => Some(InspectMode::Characters),
+            ALL_KW => Some(InspectMode::All),
+            LEADING_KW => Some(InspectMode::Leading),
+            FIRST_KW => Some(InspectMode::First),
+            _ => None,
+        }
+    }
+
+    pub fn items(&self) -> Vec<InspectItem> {
+        self.0.children().filter_map(InspectItem::cast).collect()
+    }
+}
+
+impl InspectItem {
+    fn operands(&self) -> Vec<(bool, Operand)> {
+        let mut after_by = false;
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| match el {
+                rowan::NodeOrToken::Token(t) => {
+                    after_by |= t.kind() == BY_KW;
+                    None
+                }
+                rowan::NodeOrToken::Node(n) => Operand::cast(n).map(|o| (after_by, o)),
+            })
+            .collect()
+    }
+
+    /// The value searched for; `None` for CHARACTERS.
+    pub fn pattern(&self) -> Option<Operand> {
+        self.operands().into_iter().find(|(after_by, _)| !after_by).map(|(_, o)| o)
+    }
+
+    /// The value after BY in a REPLACING phrase.
+    pub fn replacement(&self) -> Option<Operand> {
+        self.operands().into_iter().find(|(after_by, _)| *after_by).map(|(_, o)| o)
+    }
+
+    pub fn boundaries(&self) -> Vec<InspectBoundary> {
+        self.0.children().filter_map(InspectBoundary::cast).collect()
+    }
+}
+
+impl ConvertingPhrase {
+    pub fn from(&self) -> Option<Operand> {
+        self.0.children().find_map(Operand::cast)
+    }
+
+    pub fn to(&self) -> Option<Operand> {
+        self.0.children().filter_map(Operand::cast).nth(1)
+    }
+
+    pub fn boundaries(&self) -> Vec<InspectBoundary> {
+        self.0.children().filter_map(InspectBoundary::cast).collect()
+    }
+}
+
+impl InspectBoundary {
+    /// BEFORE, as opposed to AFTER.
+    pub fn is_before(&self) -> bool {
+        child_token(&self.0, BEFORE_KW).is_some()
+    }
+
+    pub fn is_initial(&self) -> bool {
+        child_token(&self.0, INITIAL_KW).is_some()
+    }
+
+    pub fn value(&self) -> Option<Operand> {
+        self.0.children().find_map(Operand::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpenMode {
+    Input,
+    Output,
+    InputOutput,
+    Extend,
+}
+
+impl OpenStmt {
+    /// The files opened, each with its mode.
+    pub fn files(&self) -> Vec<(OpenMode, String)> {
+        let mut files = Vec::new();
+        for group in self.0.children().filter(|n| n.kind() == OPEN_GROUP) {
+            let mode = match group.first_token().map(|t| t.kind()) {
+                Some(INPUT_KW) => OpenMode::Input,
+                Some(OUTPUT_KW) => OpenMode::Output,
+                Some(I_O_KW) => OpenMode::InputOutput,
+                _ => OpenMode::Extend,
+            };
+            files.extend(idents(&group).into_iter().map(|name| (mode, name)));
+        }
+        files
+    }
+}
+
+impl CloseStmt {
+    pub fn files(&self) -> Vec<String> {
+        idents(&self.0)
+    }
+}
+
+/// The name following the verb of a file I/O statement.
+fn io_name(node: &SyntaxNode) -> Option<String> {
+    child_token(node, IDENT).map(|t| t.text().to_string())
+}
+
+/// The record written by WRITE, REWRITE or RELEASE.
+fn io_record(node: &SyntaxNode) -> Option<DataRef> {
+    node.children().find_map(DataRef::cast)
+}
+
+impl ReadStmt {
+    pub fn file_name(&self) -> Option<String> {
+        io_name(&self.0)
+    }
+
+    /// `READ ... NEXT`, a sequential read of a file opened for dynamic access.
+    pub fn is_next(&self) -> bool {
+        child_token(&self.0, NEXT_KW).is_some()
+    }
+
+    pub fn target(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, INTO_PHRASE)
+    }
+
+    pub fn key(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, KEY_PHRASE)
+    }
+
+    pub fn at_end(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, AT_END)
+    }
+
+    pub fn not_at_end(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_AT_END)
+    }
+
+    pub fn invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, INVALID_KEY)
+    }
+
+    pub fn not_invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_INVALID_KEY)
+    }
+}
+
+/// The operand of the FROM phrase of `node`.
+fn from_operand(node: &SyntaxNode) -> Option<Operand> {
+    node.children()
+        .find(|n| n.kind() == FROM_PHRASE)?
+        .children()
+        .find_map(Operand::cast)
+}
+
+impl WriteStmt {
+    pub fn record_name(&self) -> Option<String> {
+        io_record(&self.0)?.name()
+    }
+
+    pub fn from(&self) -> Option<Operand> {
+        from_operand(&self.0)
+    }
+
+    pub fn advancing(&self) -> Option<AdvancingPhrase> {
+        self.0.children().find_map(AdvancingPhrase::cast)
+    }
+
+    pub fn at_end_of_page(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, AT_END_OF_PAGE)
+    }
+
+    pub fn not_at_end_of_page(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_AT_END_OF_PAGE)
+    }
+
+    pub fn invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, INVALID_KEY)
+    }
+
+    pub fn not_invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_INVALID_KEY)
+    }
+}
+
+impl AdvancingPhrase {
+    /// Advance before writing the record, as opposed to after.
+    pub fn is_before(&self) -> bool {
+        child_token(&self.0, BEFORE_KW).is_some()
+    }
+
+    pub fn is_page(&self) -> bool {
+        child_token(&self.0, PAGE_KW).is_some()
+    }
+
+    /// The number of lines, or a mnemonic name for a channel.
+    pub fn lines(&self) -> Option<Operand> {
+        self.0.children().find_map(Operand::cast)
+    }
+}
+
+impl RewriteStmt {
+    pub fn record_name(&self) -> Option<String> {
+        io_record(&self.0)?.name()
+    }
+
+    pub fn from(&self) -> Option<Operand> {
+        from_operand(&self.0)
+    }
+
+    pub fn invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, INVALID_KEY)
+    }
+
+    pub fn not_invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_INVALID_KEY)
+    }
+}
+
+impl DeleteStmt {
+    pub fn file_name(&self) -> Option<String> {
+        io_name(&self.0)
+    }
+
+    pub fn invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, INVALID_KEY)
+    }
+
+    pub fn not_invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_INVALID_KEY)
+    }
+}
+
+impl StartStmt {
+    pub fn file_name(&self) -> Option<String> {
+        io_name(&self.0)
+    }
+
+    pub fn key(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, KEY_PHRASE)
+    }
+
+    /// How records are positioned relative to the key, EQUAL when absent.
+    pub fn key_op(&self) -> RelationalOp {
+        self.0
+            .children()
+            .find(|n| n.kind() == KEY_PHRASE)
+            .and_then(|phrase| RelationalOp::from_node(&phrase.children().find(|n| n.kind() == REL_OP)?))
+            .unwrap_or(RelationalOp { comparison: Comparison::Equal, negated: false })
+    }
+
+    pub fn invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, INVALID_KEY)
+    }
+
+    pub fn not_invalid_key(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_INVALID_KEY)
+    }
+}
+
+/// The program a CALL or CANCEL refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CallTarget {
+    /// A literal program name, resolved when the program is linked.
+    Static(String),
+    /// An identifier that holds the program name at run time.
+    Dynamic(DataRef),
+}
+
+impl CallTarget {
+    fn from_operand(operand: Operand) -> Option<Self> {
+        match operand.kind() {
+            OperandKind::Literal(literal) => literal.string_value().map(CallTarget::Static),
+            OperandKind::DataRef(data_ref) => Some(CallTarget::Dynamic(data_ref)),
+            OperandKind::FunctionCall(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PassingMode {
+    Reference,
+    Content,
+    Value,
+}
+
+/// An argument of CALL or a parameter of the PROCEDURE DIVISION header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Argument {
+    pub mode: PassingMode,
+    /// `None` for OMITTED.
+    pub value: Option<Operand>,
+}
+
+/// The arguments of the USING phrase of `node`, with the passing mode each
+/// one inherits from the last BY phrase.
+fn using_arguments(node: &SyntaxNode) -> Vec<Argument> {
+    let Some(phrase) = node.children().find(|n| n.kind() == USING_PHRASE) else {
+        return Vec::new();
+    };
+    let mut mode = PassingMode::Reference;
+    phrase
+        .children()
+        .filter(|n| n.kind() == ARGUMENT)
+        .map(|argument| {
+            if child_token(&argument, CONTENT_KW).is_some() {
+                mode = PassingMode::Content;
+            } else if child_token(&argument, VALUE_KW).is_some() {
+                mode = PassingMode::Value;
+            } else if child_token(&argument, REFERENCE_KW).is_some() {
+                mode = PassingMode::Reference;
+            }
+            Argument { mode, value: argument.children().find_map(Operand::cast) }
+        })
+        .collect()
+}
+
+impl CallStmt {
+    pub fn target(&self) -> Option<CallTarget> {
+        self.0.children().find_map(Operand::cast).and_then(CallTarget::from_operand)
+    }
+
+    pub fn arguments(&self) -> Vec<Argument> {
+        using_arguments(&self.0)
+    }
+
+    pub fn returning(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, RETURNING_PHRASE)
+    }
+
+    /// Statements run when the program cannot be called. ON OVERFLOW is the
+    /// older spelling of ON EXCEPTION.
+    pub fn on_exception(&self) -> Vec<Stmt> {
+        let statements = phrase_statements(&self.0, ON_EXCEPTION);
+        if statements.is_empty() { phrase_statements(&self.0, ON_OVERFLOW) } else { statements }
+    }
+
+    pub fn not_on_exception(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_ON_EXCEPTION)
+    }
+}
+
+impl CancelStmt {
+    pub fn targets(&self) -> Vec<CallTarget> {
+        self.0.children().filter_map(Operand::cast).filter_map(CallTarget::from_operand).collect()
+    }
+}
+
+/// The data division of the program `node` belongs to.
+fn enclosing_data_division(node: &SyntaxNode) -> Option<DataDivision> {
+    node.ancestors().last().and_then(Root::cast)?.data_division()
+}
+
+/// Whether the program `node` belongs to declares `DECIMAL-POINT IS COMMA`.
+fn decimal_point_is_comma(node: &SyntaxNode) -> bool {
+    node.ancestors()
+        .last()
+        .and_then(Root::cast)
+        .and_then(|root| root.environment_division()?.configuration_section()?.special_names())
+        .is_some_and(|special_names| special_names.decimal_point_is_comma())
+}
+
+impl SearchStmt {
+    /// `SEARCH ALL`, a binary search over a table with KEY phrases.
+    pub fn is_all(&self) -> bool {
+        child_token(&self.0, ALL_KW).is_some()
+    }
+
+    pub fn table(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    /// The data description of the table searched.
+    pub fn table_item(&self) -> Option<DataItem> {
+        enclosing_data_division(&self.0)?.resolve(&self.table()?).ok()
+    }
+
+    pub fn varying(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, SEARCH_VARYING)
+    }
+
+    /// The index stepped through the table: the first index of its INDEXED
+    /// BY phrase, which VARYING may name explicitly.
+    pub fn index(&self) -> Option<String> {
+        let table = self.table_item()?;
+        match self.varying().and_then(|v| v.name()) {
+            Some(varying) if is_index_of(&table, &varying) => Some(varying),
+            _ => table.occurs_clause()?.indexed_by().into_iter().next(),
+        }
+    }
+
+    pub fn at_end(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, AT_END)
+    }
+
+    pub fn whens(&self) -> impl Iterator<Item = SearchWhen> + '_ {
+        self.0.children().filter_map(SearchWhen::cast)
+    }
+}
+
+fn is_index_of(table: &DataItem, name: &str) -> bool {
+    table
+        .occurs_clause()
+        .is_some_and(|o| o.indexed_by().iter().any(|i| i.eq_ignore_ascii_case(name)))
+}
+
+impl SearchWhen {
+    pub fn condition(&self) -> Option<Condition> {
+        self.0.children().find_map(Condition::cast)
+    }
+
+    pub fn statements(&self) -> Vec<Stmt> {
+        self.0.children().filter_map(Stmt::cast).collect()
+    }
+}
+
+/// A receiving field of SET.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SetTarget {
+    /// An index, index data item, condition name or pointer.
+    Item(DataRef),
+    /// `ADDRESS OF identifier`, pointing a linkage item at storage.
+    AddressOf(DataRef),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SetValue {
+    Operand(Operand),
+    /// `TRUE`, setting a condition name; `FALSE` is its counterpart.
+    True,
+    False,
+    AddressOf(DataRef),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SetAction {
+    To(SetValue),
+    UpBy(Operand),
+    DownBy(Operand),
+}
+
+impl SetStmt {
+    pub fn targets(&self) -> Vec<SetTarget> {
+        self.0
+            .children_with_tokens()
+            .take_while(|el| !matches!(el.kind(), TO_KW | UP_KW | DOWN_KW))
+            .filter_map(|el| el.into_node())
+            .filter_map(|node| match node.kind() {
+                DATA_REF => Some(SetTarget::Item(DataRef(node))),
+                ADDRESS_OF => node.children().find_map(DataRef::cast).map(SetTarget::AddressOf),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn action(&self) -> Option<SetAction> {
+        let mut rest = self
+            .0
+            .children_with_tokens()
+            .filter(|el| !el.kind().is_trivia())
+            .skip_while(|el| !matches!(el.kind(), TO_KW | UP_KW | DOWN_KW));
+        let verb = rest.next()?.kind();
+        let value = rest.find(|el| el.kind() != BY_KW)?;
+        match (verb, value.kind()) {
+            (TO_KW, TRUE_KW) => Some(SetAction::To(SetValue::True)),
+            (TO_KW, FALSE_KW) => Some(SetAction::To(SetValue::False)),
+            (TO_KW, ADDRESS_OF) => {
+                let data_ref = value.into_node()?.children().find_map(DataRef::cast)?;
+                Some(SetAction::To(SetValue::AddressOf(data_ref)))
+            }
+            (TO_KW, _) => Operand::cast(value.into_node()?).map(|o| SetAction::To(SetValue::Operand(o))),
+            (UP_KW, _) => Operand::cast(value.into_node()?).map(SetAction::UpBy),
+            (_, _) => Operand::cast(value.into_node()?).map(SetAction::DownBy),
+        }
+    }
+
+    /// The tables indexed by the targets, for targets that are indexes.
+    pub fn indexed_tables(&self) -> Vec<DataItem> {
+        let Some(data) = enclosing_data_division(&self.0) else { return Vec::new() };
+        self.targets()
+            .into_iter()
+            .filter_map(|target| match target {
+                SetTarget::Item(data_ref) => data.indexed_table(&data_ref.name()?),
+                SetTarget::AddressOf(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// The sort file of SORT or MERGE followed by its USING and GIVING files.
+fn sort_files(node: &SyntaxNode) -> Vec<String> {
+    let mut files: Vec<_> = child_token(node, IDENT).map(|t| t.text().to_string()).into_iter().collect();
+    for phrase in node.children().filter(|n| matches!(n.kind(), SORT_USING | SORT_GIVING)) {
+        files.extend(idents(&phrase));
+    }
+    files
+}
+
+fn sort_keys(node: &SyntaxNode) -> Vec<(KeyOrder, String)> {
+    let mut keys = Vec::new();
+    for phrase in node.children().filter(|n| n.kind() == SORT_KEY) {
+        let order = if child_token(&phrase, DESCENDING_KW).is_some() {
+            KeyOrder::Descending
+        } else {
+            KeyOrder::Ascending
+        };
+        keys.extend(phrase.children().filter_map(DataRef::cast).filter_map(|key| Some((order, key.name()?))));
+    }
+    keys
+}
+
+fn phrase_idents(node: &SyntaxNode, kind: SyntaxKind) -> Vec<String> {
+    node.children().find(|n| n.kind() == kind).map(|n| idents(&n)).unwrap_or_default()
+}
+
+fn phrase_procedure(node: &SyntaxNode, kind: SyntaxKind) -> Option<ProcedureRange> {
+    node.children().find(|n| n.kind() == kind)?.children().find_map(ProcedureRange::cast)
+}
+
+impl SortStmt {
+    /// The SD file sorted through.
+    pub fn file_name(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    /// Key names from major to minor, with their direction.
+    pub fn keys(&self) -> Vec<(KeyOrder, String)> {
+        sort_keys(&self.0)
+    }
+
+    pub fn has_duplicates_in_order(&self) -> bool {
+        child_token(&self.0, DUPLICATES_KW).is_some()
+    }
+
+    pub fn using(&self) -> Vec<String> {
+        phrase_idents(&self.0, SORT_USING)
+    }
+
+    pub fn giving(&self) -> Vec<String> {
+        phrase_idents(&self.0, SORT_GIVING)
+    }
+
+    /// The procedure that RELEASEs the records to sort, instead of USING.
+    pub fn input_procedure(&self) -> Option<ProcedureRange> {
+        phrase_procedure(&self.0, INPUT_PROCEDURE)
+    }
+
+    /// The procedure that RETURNs the sorted records, instead of GIVING.
+    pub fn output_procedure(&self) -> Option<ProcedureRange> {
+        phrase_procedure(&self.0, OUTPUT_PROCEDURE)
+    }
+}
+
+impl MergeStmt {
+    pub fn file_name(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    pub fn keys(&self) -> Vec<(KeyOrder, String)> {
+        sort_keys(&self.0)
+    }
+
+    pub fn using(&self) -> Vec<String> {
+        phrase_idents(&self.0, SORT_USING)
+    }
+
+    pub fn giving(&self) -> Vec<String> {
+        phrase_idents(&self.0, SORT_GIVING)
+    }
+
+    pub fn output_procedure(&self) -> Option<ProcedureRange> {
+        phrase_procedure(&self.0, OUTPUT_PROCEDURE)
+    }
+}
+
+impl ReleaseStmt {
+    pub fn record_name(&self) -> Option<String> {
+        io_record(&self.0)?.name()
+    }
+
+    pub fn from(&self) -> Option<Operand> {
+        from_operand(&self.0)
+    }
+}
+
+impl ReturnStmt {
+    pub fn file_name(&self) -> Option<String> {
+        io_name(&self.0)
+    }
+
+    pub fn target(&self) -> Option<DataRef> {
+        phrase_data_ref(&self.0, INTO_PHRASE)
+    }
+
+    pub fn at_end(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, AT_END)
+    }
+
+    pub fn not_at_end(&self) -> Vec<Stmt> {
+        phrase_statements(&self.0, NOT_AT_END)
+    }
+}
+
+impl DisplayStmt {
+    /// The literals and identifiers displayed, in order.
+    pub fn operands(&self) -> Vec<Operand> {
+        self.0.children().filter_map(Operand::cast).collect()
+    }
+
+    /// The value of the first operand when it is an alphanumeric literal,
+    /// with fragments from continuation lines joined.
+    pub fn string_literal(&self) -> Option<String> {
+        match self.operands().first()?.as_literal()?.value()? {
+            LiteralValue::Alphanumeric(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Mnemonic or device name of the UPON phrase.
+    pub fn upon(&self) -> Option<String> {
+        child_token(&self.0, IDENT).map(|t| t.text().to_string())
+    }
+
+    /// `WITH NO ADVANCING`: the cursor stays on the line.
+    pub fn is_no_advancing(&self) -> bool {
+        child_token(&self.0, NO_KW).is_some()
+    }
+}
+
+/// Where ACCEPT takes its data from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AcceptSource {
+    /// No FROM phrase: the system input device.
+    Input,
+    /// `DATE`, as YYMMDD or with `YYYYMMDD` as YYYYMMDD.
+    Date { four_digit_year: bool },
+    /// `DAY`, as YYDDD or with `YYYYDDD` as YYYYDDD.
+    Day { four_digit_year: bool },
+    DayOfWeek,
+    Time,
+    /// `ENVIRONMENT name`, the value of an environment variable.
+    Environment(Operand),
+    /// A device or mnemonic name such as `CONSOLE` or `SYSIN`.
+    Device(String),
+}
+
+impl AcceptStmt {
+    pub fn target(&self) -> Option<DataRef> {
+        self.0.children().find_map(DataRef::cast)
+    }
+
+    pub fn source(&self) -> AcceptSource {
+        let tokens: Vec<_> = self
+            .0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .filter(|t| !t.kind().is_trivia())
+            .skip_while(|t| t.kind() != FROM_KW)
+            .skip(1)
+            .collect();
+        let four_digit_year = tokens
+            .get(1)
+            .is_some_and(|t| t.kind() == IDENT && ["YYYYMMDD", "YYYYDDD"].iter().any(|w| t.text().eq_ignore_ascii_case(w)));
+        match tokens.first().map(|t| (t.kind(), t)) {
+            Some((DATE_KW, _)) => AcceptSource::Date { four_digit_year },
+            Some((DAY_KW, _)) => AcceptSource::Day { four_digit_year },
+            Some((DAY_OF_WEEK_KW, _)) => AcceptSource::DayOfWeek,
+            Some((TIME_KW, _)) => AcceptSource::Time,
+            Some((IDENT, device)) => AcceptSource::Device(device.text().to_string()),
+            Some((ENVIRONMENT_KW, _)) => match self.0.children().filter_map(Operand::cast).nth(1) {
+                Some(name) => AcceptSource::Environment(name),
+                None => AcceptSource::Input,
+            },
+            _ => AcceptSource::Input,
+        }
+    }
+}
+
+/// Decoded value of a [`Literal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralValue {
+    Alphanumeric(String),
+    /// `N"..."`
+    National(String),
+    /// `Z"..."`, without the implied trailing NUL
+    NullTerminated(String),
+    /// `X"..."`
+    Hex(Vec<u8>),
+    Numeric(NumericValue),
+    Figurative(Figurative),
+    /// `ALL literal`, repeated to fill the receiving item
+    All(Box<LiteralValue>),
+}
+
+/// A numeric literal as an unscaled integer: `-12.50` is `-1250` with scale 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumericValue {
+    pub value: i128,
+    pub scale: u32,
+}
+
+impl NumericValue {
+    fn from_literal(text: &str) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (integer, fraction) = digits.split_once(['.', ',']).unwrap_or((digits, ""));
+        let value: i128 = format!("{integer}{fraction}").parse().ok()?;
+        Some(Self {
+            value: if negative { -value } else { value },
+            scale: fraction.len() as u32,
+        })
+    }
+
+    pub fn as_integer(self) -> Option<i128> {
+        (self.scale == 0).then_some(self.value)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Figurative {
+    Zero,
+    Space,
+    HighValue,
+    LowValue,
+    Quote,
+    Null,
+}
+
+impl Literal {
+    fn tokens(&self) -> Vec<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|el| el.into_token())
+            .filter(|t| !t.kind().is_trivia())
+            .collect()
+    }
+
+    pub fn is_all(&self) -> bool {
+        self.tokens().first().is_some_and(|t| t.kind() == ALL_KW)
+    }
+
+    pub fn value(&self) -> Option<LiteralValue> {
+        let tokens = self.tokens();
+        match tokens.split_first()? {
+            (first, rest) if first.kind() == ALL_KW => {
+                Some(LiteralValue::All(Box::new(decode_literal(rest)?)))
+            }
+            _ => decode_literal(&tokens),
+        }
+    }
+
+    pub fn string_value(&self) -> Option<String> {
+        match self.value()? {
+            LiteralValue::Alphanumeric(s)
+            | LiteralValue::National(s)
+            | LiteralValue::NullTerminated(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn numeric_value(&self) -> Option<NumericValue> {
+        match self.value()? {
+            LiteralValue::Numeric(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn figurative(&self) -> Option<Figurative> {
+        match self.value()? {
+            LiteralValue::Figurative(f) => Some(f),
+            LiteralValue::All(inner) => match *inner {
+                LiteralValue::Figurative(f) => Some(f),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn decode_literal(tokens: &[SyntaxToken]) -> Option<LiteralValue> {
+    let first = tokens.first()?;
+    let value = match first.kind() {
+        STRING_LITERAL => LiteralValue::Alphanumeric(quoted_content(tokens)),
+        NATIONAL_LITERAL => LiteralValue::National(quoted_content(tokens)),
+        NULL_TERMINATED_LITERAL => LiteralValue::NullTerminated(quoted_content(tokens)),
+        HEX_LITERAL => LiteralValue::Hex(decode_hex(&quoted_content(tokens))?),
+        INTEGER_LITERAL | DECIMAL_LITERAL => {
+            LiteralValue::Numeric(NumericValue::from_literal(first.text())?)
+        }
+        ZERO_KW => LiteralValue::Figurative(Figurative::Zero),
+        SPACE_KW => LiteralValue::Figurative(Figurative::Space),
+        HIGH_VALUE_KW => LiteralValue::Figurative(Figurative::HighValue),
+        LOW_VALUE_KW => LiteralValue::Figurative(Figurative::LowValue),
+        QUOTE_KW => LiteralValue::Figurative(Figurative::Quote),
+        NULL_KW => LiteralValue::Figurative(Figurative::Null),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Content of a quoted literal, with the pieces resumed on continuation
+/// lines joined and doubled quotes collapsed. A piece without its closing
+/// quote runs through column 72, trailing spaces included, and is padded
+/// with spaces when its line ends earlier.
+fn quoted_content(tokens: &[SyntaxToken]) -> String {
+    let mut quote = '"';
+    let mut content = String::new();
+    for token in tokens {
+        let text = token.text();
+        let Some(start) = text.find(['"', '\'']) else { continue };
+        let quoted = &text[start..];
+        quote = quoted.chars().next().unwrap();
+        let (len, terminated) = scan_string(quoted);
+        if terminated {
+            content.push_str(&quoted[1..len - 1]);
+        } else {
+            content.push_str(&quoted[1..]);
+            if let Some(column) = fixed_format_column(token) {
+                let end = column + text.chars().count();
+                content.extend(std::iter::repeat_n(' ', PROGRAM_TEXT_END.saturating_sub(end)));
+            }
+        }
+    }
+    content.replace(&format!("{quote}{quote}"), &quote.to_string())
+}
+
+/// The 0-based column `token` starts at, if its line is in fixed format.
+fn fixed_format_column(token: &SyntaxToken) -> Option<usize> {
+    let before: Vec<_> =
+        std::iter::successors(token.prev_token(), |t| t.prev_token()).take_while(|t| t.kind() != NEWLINE).collect();
+    before
+        .iter()
+        .any(|t| matches!(t.kind(), SEQUENCE_AREA | INDICATOR))
+        .then(|| before.iter().map(|t| t.text().chars().count()).sum())
+}
+
+fn decode_hex(digits: &str) -> Option<Vec<u8>> {
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+impl Parse {
+    pub fn root(&self) -> Option<Root> {
+        Root::cast(self.syntax())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::picture::PictureCategory;
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_cobol() {
+        let source = r#"
+IDENTIFICATION DIVISION.
+PROGRAM-ID. HELLO.
+PROCEDURE DIVISION.
+    DISPLAY "Hello, World!".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+
+        let root = parse.root().expect("Should have root");
+
+        // Check IDENTIFICATION DIVISION
+        assert!(root.identification_division().is_some());
+
+        // Check PROGRAM-ID
+        let program_id = root.program_id().expect("Should have PROGRAM-ID");
+        assert_eq!(program_id.name(), Some("HELLO".to_string()));
+
+        // Check PROCEDURE DIVISION
+        let proc_div = root.procedure_division().expect("Should have PROCEDURE DIVISION");
+        let displays: Vec<_> = proc_div.display_statements().collect();
+        assert_eq!(displays.len(), 1);
+        assert_eq!(displays[0].string_literal(), Some("Hello, World!".to_string()));
+    }
+
+    #[test]
+    fn test_parse_multiple_display() {
+        let source = r#"
+IDENTIFICATION DIVISION.
+PROGRAM-ID. MULTI.
+PROCEDURE DIVISION.
+    DISPLAY "First".
+    DISPLAY "Second".
+    DISPLAY "Third".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+
+        let root = parse.root().unwrap();
+        let proc_div = root.procedure_division().unwrap();
+        let displays: Vec<_> = proc_div.display_statements().collect();
+
+        assert_eq!(displays.len(), 3);
+        assert_eq!(displays[0].string_literal(), Some("First".to_string()));
+        assert_eq!(displays[1].string_literal(), Some("Second".to_string()));
+        assert_eq!(displays[2].string_literal(), Some("Third".to_string()));
+    }
+
+    #[test]
+    fn test_parse_fixed_format() {
+        let source = "\
+000100 IDENTIFICATION DIVISION.                                         HELLO001
+000200 PROGRAM-ID. HELLO.                                               HELLO002
+000300 PROCEDURE DIVISION.                                              HELLO003
+000400     DISPLAY \"Hello, World!\".                                     HELLO004
+";
+
+        let parse = parse_with_options(source, SourceOptions { format: SourceFormat::Fixed, ..SourceOptions::default() });
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        assert_eq!(root.program_id().unwrap().name(), Some("HELLO".to_string()));
+        let proc_div = root.procedure_division().unwrap();
+        let displays: Vec<_> = proc_div.display_statements().collect();
+        assert_eq!(displays.len(), 1);
+        assert_eq!(displays[0].string_literal(), Some("Hello, World!".to_string()));
+
+        let kinds: Vec<_> = parse
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|el| el.into_token())
+            .take(4)
+            .map(|t| (t.kind(), t.text().to_string()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (SEQUENCE_AREA, "000100".to_string()),
+                (INDICATOR, " ".to_string()),
+                (IDENTIFICATION_KW, "IDENTIFICATION".to_string()),
+                (WHITESPACE, " ".to_string()),
+            ]
+        );
+        let ident_areas = parse
+            .syntax()
+            .descendants_with_tokens()
+            .filter(|el| el.kind() == IDENTIFICATION_AREA)
+            .count();
+        assert_eq!(ident_areas, 4);
+    }
+
+    #[test]
+    fn test_comments_are_trivia() {
+        let source = r#"
+IDENTIFICATION DIVISION. *> header
+PROGRAM-ID. COMMENTS.
+PROCEDURE DIVISION.
+*> Greets the user.
+*> Twice, to be sure.
+    DISPLAY "Hello". *> trailing note
+
+    *> detached comment
+
+    DISPLAY "Bye".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let displays: Vec<_> = root.procedure_division().unwrap().display_statements().collect();
+        assert_eq!(displays.len(), 2);
+        assert_eq!(
+            displays[0].leading_comments(),
+            vec!["Greets the user.".to_string(), "Twice, to be sure.".to_string()]
+        );
+        assert!(displays[1].leading_comments().is_empty());
+    }
+
+    #[test]
+    fn test_continued_literal() {
+        let source = "\
+000100 PROCEDURE DIVISION.
+000200     DISPLAY \"THIS LITERAL IS LONGER THAN ONE LINE OF FIXED-FORMAT
+000300-        \" SOURCE TEXT\".
+";
+
+        let parse = parse_with_options(source, SourceOptions { format: SourceFormat::Fixed, ..SourceOptions::default() });
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let displays: Vec<_> = root.procedure_division().unwrap().display_statements().collect();
+        assert_eq!(displays.len(), 1);
+        assert_eq!(
+            displays[0].string_literal(),
+            Some("THIS LITERAL IS LONGER THAN ONE LINE OF FIXED-FORMAT SOURCE TEXT".to_string())
+        );
+
+        // A line that stops short of column 72 is padded with spaces
+        let source = "000100 PROCEDURE DIVISION.\n000200     DISPLAY \"SHORT\n000300-    \"LINE\".\n";
+        let parse = parse_with_options(source, SourceOptions { format: SourceFormat::Fixed, ..SourceOptions::default() });
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        let display = parse.root().unwrap().procedure_division().unwrap().display_statements().next().unwrap();
+        assert_eq!(display.string_literal(), Some(format!("SHORT{}LINE", " ".repeat(72 - 25))));
+
+        // Free format has no continuation lines, so the literal is unterminated
+        let free = super::parse("PROCEDURE DIVISION.\n    DISPLAY \"OPEN\n    \"NEXT\".\n");
+        assert_eq!(free.errors, vec!["Unterminated literal \"OPEN".to_string()]);
+    }
+
+    #[test]
+    fn test_literals() {
+        let source = r#"
+PROCEDURE DIVISION.
+    DISPLAY 'IT''S'.
+    DISPLAY "SAY ""HI""".
+    DISPLAY X"C1c2".
+    DISPLAY N"KANJI".
+    DISPLAY Z"C-STR".
+    DISPLAY -12.50.
+    DISPLAY +7.
+    DISPLAY 0042.
+    DISPLAY HIGH-VALUES.
+    DISPLAY ALL "*-".
+    DISPLAY ALL ZEROS.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+
+        let root = parse.root().unwrap();
+        let values: Vec<_> = root
+            .procedure_division()
+            .unwrap()
+            .display_statements()
+            .map(|d| d.operands()[0].as_literal().unwrap().value().unwrap())
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                LiteralValue::Alphanumeric("IT'S".to_string()),
+                LiteralValue::Alphanumeric("SAY \"HI\"".to_string()),
+                LiteralValue::Hex(vec![0xC1, 0xC2]),
+                LiteralValue::National("KANJI".to_string()),
+                LiteralValue::NullTerminated("C-STR".to_string()),
+                LiteralValue::Numeric(NumericValue { value: -1250, scale: 2 }),
+                LiteralValue::Numeric(NumericValue { value: 7, scale: 0 }),
+                LiteralValue::Numeric(NumericValue { value: 42, scale: 0 }),
+                LiteralValue::Figurative(Figurative::HighValue),
+                LiteralValue::All(Box::new(LiteralValue::Alphanumeric("*-".to_string()))),
+                LiteralValue::All(Box::new(LiteralValue::Figurative(Figurative::Zero))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decimal_point_is_comma() {
+        let source = "PROCEDURE DIVISION.\n    DISPLAY 2,5.\n    DISPLAY 3.\n";
+        let options = SourceOptions { decimal_point_is_comma: true, ..SourceOptions::default() };
+
+        let parse = parse_with_options(source, options);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        let root = parse.root().unwrap();
+        let values: Vec<_> = root
+            .procedure_division()
+            .unwrap()
+            .display_statements()
+            .map(|d| d.operands()[0].as_literal().unwrap().numeric_value().unwrap())
+            .collect();
+        assert_eq!(values, vec![NumericValue { value: 25, scale: 1 }, NumericValue { value: 3, scale: 0 }]);
+        assert_eq!(values[0].to_f64(), 2.5);
+    }
+
+    #[test]
+    fn test_unterminated_literal() {
+        let parse = parse("PROCEDURE DIVISION.\n    DISPLAY \"OOPS.\n    DISPLAY \"OK\".\n");
+        assert_eq!(parse.errors, vec!["Unterminated literal \"OOPS.".to_string()]);
+        let root = parse.root().unwrap();
+        let displays: Vec<_> = root.procedure_division().unwrap().display_statements().collect();
+        assert_eq!(displays[1].string_literal(), Some("OK".to_string()));
+    }
+
+    #[test]
+    fn test_parse_data_division() {
+        let source = r#"
+IDENTIFICATION DIVISION.
+PROGRAM-ID. DATA-DEMO.
+DATA DIVISION.
+FILE SECTION.
+FD CUSTOMER-FILE.
+01 CUSTOMER-RECORD.
+   05 CUST-ID PIC 9(6).
+   05 CUST-NAME PIC X(30).
+WORKING-STORAGE SECTION.
+01 WS-RECORD.
+   05 WS-HEADER.
+      10 WS-CODE PIC X(2).
+         88 WS-CODE-OK VALUE "OK".
+      10 FILLER PIC X(8).
+   05 WS-AMOUNT PIC 9(5)V99.
+01 WS-OTHER PIC X.
+66 WS-ALIAS RENAMES WS-CODE.
+77 WS-COUNT PIC 9(4).
+LOCAL-STORAGE SECTION.
+01 LS-TEMP PIC X.
+LINKAGE SECTION.
+01 LK-PARM PIC X(10).
+PROCEDURE DIVISION.
+    DISPLAY "Done".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let data = root.data_division().expect("Should have DATA DIVISION");
+
+        let fd = data.file_section().unwrap().file_descriptions().next().unwrap();
+        assert_eq!(fd.name(), Some("CUSTOMER-FILE".to_string()));
+        let record = fd.records().next().unwrap();
+        assert_eq!(record.name(), Some("CUSTOMER-RECORD".to_string()));
+        assert_eq!(record.children().count(), 2);
+
+        let items: Vec<_> = data.working_storage_section().unwrap().items().collect();
+        let top: Vec<_> = items.iter().map(|i| (i.level(), i.name())).collect();
+        assert_eq!(
+            top,
+            vec![
+                (Some(1), Some("WS-RECORD".to_string())),
+                (Some(1), Some("WS-OTHER".to_string())),
+                (Some(66), Some("WS-ALIAS".to_string())),
+                (Some(77), Some("WS-COUNT".to_string())),
+            ]
+        );
+
+        let record: Vec<_> = items[0].children().collect();
+        assert_eq!(record.len(), 2);
+        assert!(record[0].is_group());
+        assert!(!record[1].is_group());
+        let header: Vec<_> = record[0].children().collect();
+        assert_eq!(header[0].name(), Some("WS-CODE".to_string()));
+        assert!(header[1].is_filler());
+        let conditions: Vec<_> = header[0].children().collect();
+        assert_eq!(conditions[0].level(), Some(88));
+        assert_eq!(conditions[0].name(), Some("WS-CODE-OK".to_string()));
+
+        assert_eq!(data.local_storage_section().unwrap().items().count(), 1);
+        assert_eq!(data.linkage_section().unwrap().items().count(), 1);
+        assert_eq!(root.procedure_division().unwrap().display_statements().count(), 1);
+    }
+
+    #[test]
+    fn test_picture_clauses() {
+        let source = r#"
+DATA DIVISION.
+WORKING-STORAGE SECTION.
+01 WS-AMOUNT PIC S9(7)V99.
+01 WS-EDITED PICTURE IS Z,ZZ9.99-.
+01 WS-NAME PIC X(30).
+01 WS-BAD PIC 9(5.
+"#;
+
+        let parse = parse(source);
+        assert_eq!(
+            parse.errors,
+            vec!["Invalid PICTURE string 9(5: unclosed repetition count at position 2".to_string()]
+        );
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let items: Vec<_> = root.data_division().unwrap().working_storage_section().unwrap().items().collect();
+        assert_eq!(items.len(), 4);
+
+        let amount = items[0].picture().unwrap();
+        assert_eq!(amount.category, PictureCategory::Numeric);
+        assert_eq!((amount.integer_digits, amount.fraction_digits, amount.signed), (7, 2, true));
+
+        let edited = items[1].picture_clause().unwrap();
+        assert_eq!(edited.picture_string(), Some("Z,ZZ9.99-".to_string()));
+        assert_eq!(edited.picture().unwrap().category, PictureCategory::NumericEdited);
+
+        assert_eq!(items[2].picture().unwrap().size, 30);
+        assert!(items[3].picture().is_none());
+    }
+
+    #[test]
+    fn test_data_description_clauses() {
+        let source = r#"
+DATA DIVISION.
+WORKING-STORAGE SECTION.
+01 WS-COUNT PIC S9(4) USAGE IS COMP VALUE ZERO.
+01 WS-PACKED PIC S9(7)V99 COMP-3 VALUE -12.50.
+01 WS-STATUS PIC X VALUE "N".
+   88 WS-DONE VALUE "Y".
+   88 WS-VALID VALUES ARE "A" THRU "M" "X".
+01 WS-TABLE.
+   05 WS-ENTRY OCCURS 1 TO 50 TIMES DEPENDING ON WS-COUNT
+        ASCENDING KEY IS WS-KEY INDEXED BY WS-IDX WS-IDX2.
+      10 WS-KEY PIC X(5).
+01 WS-ALT REDEFINES WS-TABLE PIC X(250).
+01 WS-TITLE PIC X(20) JUST RIGHT.
+01 WS-SIGNED PIC S9(3) SIGN LEADING SEPARATE.
+01 WS-EDIT PIC ZZ9 BLANK WHEN ZERO.
+01 WS-BIN PIC 9(9) BINARY SYNC.
+01 WS-PTR USAGE POINTER.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let items: Vec<_> = root.data_division().unwrap().working_storage_section().unwrap().items().collect();
+
+        assert_eq!(items[0].usage(), Some(Usage::Binary));
+        let zero = items[0].value_clause().unwrap().literal().unwrap();
+        assert_eq!(zero.figurative(), Some(Figurative::Zero));
+
+        assert_eq!(items[1].usage(), Some(Usage::PackedDecimal));
+        assert_eq!(
+            items[1].value_clause().unwrap().literal().unwrap().numeric_value(),
+            Some(NumericValue { value: -1250, scale: 2 })
+        );
+
+        let conditions: Vec<_> = items[2].children().collect();
+        let entries = conditions[1].value_clause().unwrap().entries();
+        assert_eq!(entries.len(), 2);
+        match &entries[0] {
+            ValueEntry::Range(from, to) => {
+                assert_eq!(from.string_value(), Some("A".to_string()));
+                assert_eq!(to.string_value(), Some("M".to_string()));
+            }
+            other => panic!("Expected range, got {:?}", other),
+        }
+
+        let entry = items[3].children().next().unwrap();
+        let occurs = entry.occurs_clause().unwrap();
+        assert_eq!((occurs.min(), occurs.max()), (Some(1), Some(50)));
+        assert_eq!(occurs.depending_on(), Some("WS-COUNT".to_string()));
+        assert_eq!(occurs.keys(), vec![(KeyOrder::Ascending, "WS-KEY".to_string())]);
+        assert_eq!(occurs.indexed_by(), vec!["WS-IDX".to_string(), "WS-IDX2".to_string()]);
+        assert_eq!(entry.children().count(), 1);
+
+        assert_eq!(items[4].redefines(), Some("WS-TABLE".to_string()));
+        assert!(items[5].justified_clause().unwrap().is_right());
+        let sign = items[6].sign_clause().unwrap();
+        assert!(sign.is_leading() && sign.is_separate());
+        assert!(items[7].is_blank_when_zero());
+        assert!(!items[8].synchronized_clause().unwrap().is_left());
+        assert_eq!(items[9].usage(), Some(Usage::Pointer));
+    }
+
+    #[test]
+    fn test_parse_environment_division() {
+        let source = r#"
+IDENTIFICATION DIVISION.
+PROGRAM-ID. ENVDEMO.
+ENVIRONMENT DIVISION.
+CONFIGURATION SECTION.
+SOURCE-COMPUTER. IBM-370.
+OBJECT-COMPUTER. IBM-370.
+SPECIAL-NAMES.
+    CONSOLE IS CRT
+    DECIMAL-POINT IS COMMA.
+INPUT-OUTPUT SECTION.
+FILE-CONTROL.
+    SELECT CUSTOMER-FILE ASSIGN TO "customers.dat"
+        ORGANIZATION IS INDEXED
+        ACCESS MODE IS DYNAMIC
+        RECORD KEY IS CUST-ID
+        ALTERNATE RECORD KEY IS CUST-NAME WITH DUPLICATES
+        FILE STATUS IS WS-STATUS.
+    SELECT OPTIONAL REPORT-FILE ASSIGN TO PRINTER
+        ORGANIZATION IS LINE SEQUENTIAL.
+DATA DIVISION.
+WORKING-STORAGE SECTION.
+01 WS-RATE PIC 9V99 VALUE 1,25.
+01 WS-AMOUNT PIC Z.ZZ9,99.
+PROCEDURE DIVISION.
+    DISPLAY 3,5.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let env = root.environment_division().expect("Should have ENVIRONMENT DIVISION");
+        let config = env.configuration_section().unwrap();
+        assert_eq!(config.source_computer(), Some("IBM-370".to_string()));
+        assert_eq!(config.object_computer(), Some("IBM-370".to_string()));
+        let special = config.special_names().unwrap();
+        assert!(special.decimal_point_is_comma());
+        assert_eq!(special.mnemonic_names(), vec![("CONSOLE".to_string(), "CRT".to_string())]);
+
+        let selects: Vec<_> = env.input_output_section().unwrap().file_control().unwrap().select_entries().collect();
+        assert_eq!(selects.len(), 2);
+        let customer = &selects[0];
+        assert_eq!(customer.file_name(), Some("CUSTOMER-FILE".to_string()));
+        assert_eq!(customer.assign_to(), Some("customers.dat".to_string()));
+        assert_eq!(customer.organization(), Organization::Indexed);
+        assert_eq!(customer.access_mode(), AccessMode::Dynamic);
+        assert_eq!(customer.record_key(), Some("CUST-ID".to_string()));
+        assert_eq!(customer.alternate_keys(), vec![("CUST-NAME".to_string(), true)]);
+        assert_eq!(customer.file_status(), Some("WS-STATUS".to_string()));
+        let report = &selects[1];
+        assert!(report.is_optional());
+        assert_eq!(report.assign_to(), Some("PRINTER".to_string()));
+        assert_eq!(report.organization(), Organization::LineSequential);
+        assert_eq!(report.access_mode(), AccessMode::Sequential);
+
+        // DECIMAL-POINT IS COMMA applies to the literals and PICTURE strings that follow
+        let items: Vec<_> = root.data_division().unwrap().working_storage_section().unwrap().items().collect();
+        assert_eq!(
+            items[0].value_clause().unwrap().literal().unwrap().numeric_value(),
+            Some(NumericValue { value: 125, scale: 2 })
+        );
+        let amount = items[1].picture().unwrap();
+        assert_eq!((amount.integer_digits, amount.fraction_digits), (4, 2));
+        let display = root.procedure_division().unwrap().display_statements().next().unwrap();
+        assert_eq!(
+            display.operands()[0].as_literal().unwrap().numeric_value(),
+            Some(NumericValue { value: 35, scale: 1 })
+        );
+    }
+
+    #[test]
+    fn test_identification_paragraphs() {
+        let source = r#"
+ID DIVISION.
+PROGRAM-ID. "PAYROLL" IS COMMON INITIAL PROGRAM.
+AUTHOR. J. O'NEILL, DATA PROCESSING.
+INSTALLATION. HEAD OFFICE.
+DATE-WRITTEN. 01/02/1987.
+DATE-COMPILED.
+SECURITY. NONE.
+REMARKS. COMPUTES THE PAYROLL;
+DATA PROCESSING DEPT.
+AUTHOR OF RECORD: J. O'NEILL.
+PROCEDURE DIVISION.
+    DISPLAY "OK".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let program_id = root.program_id().unwrap();
+        assert_eq!(program_id.name(), Some("PAYROLL".to_string()));
+        assert!(program_id.is_common() && program_id.is_initial() && !program_id.is_recursive());
+
+        let id = root.identification_division().unwrap();
+        assert_eq!(id.author(), Some("J. O'NEILL, DATA PROCESSING.".to_string()));
+        assert_eq!(id.installation(), Some("HEAD OFFICE.".to_string()));
+        assert_eq!(id.date_written(), Some("01/02/1987.".to_string()));
+        assert_eq!(id.date_compiled(), Some(String::new()));
+        assert_eq!(id.security(), Some("NONE.".to_string()));
+        // Only a real header ends the entry, not a line starting with a keyword
+        assert_eq!(
+            id.remarks(),
+            Some("COMPUTES THE PAYROLL; DATA PROCESSING DEPT. AUTHOR OF RECORD: J. O'NEILL.".to_string())
+        );
+        assert_eq!(root.procedure_division().unwrap().display_statements().count(), 1);
+
+        // In fixed format a header must begin in Area A
+        let source = "000100 IDENTIFICATION DIVISION.\n000200 PROGRAM-ID. FIXED.\n000300 AUTHOR. J SMITH\n\
+                      000400     DATA DIVISION SUPPORT.\n000500 PROCEDURE DIVISION.\n";
+        let fixed = parse_with_options(source, SourceOptions { format: SourceFormat::Fixed, ..SourceOptions::default() });
+        assert!(fixed.errors.is_empty(), "Errors: {:?}", fixed.errors);
+        let id = fixed.root().unwrap().identification_division().unwrap();
+        assert_eq!(id.author(), Some("J SMITH DATA DIVISION SUPPORT.".to_string()));
+        assert!(fixed.root().unwrap().procedure_division().is_some());
+
+        let recursive = super::parse("IDENTIFICATION DIVISION.\nPROGRAM-ID. FACT RECURSIVE.\n");
+        assert!(recursive.errors.is_empty(), "Errors: {:?}", recursive.errors);
+        assert!(recursive.root().unwrap().program_id().unwrap().is_recursive());
+    }
+
+    #[test]
+    fn test_sections_and_paragraphs() {
+        let source = r#"
+PROCEDURE DIVISION.
+    DISPLAY "START".
+MAIN-LOGIC SECTION.
+    DISPLAY "IN SECTION".
+1000-INIT.
+    DISPLAY "A" DISPLAY "B".
+    DISPLAY "C".
+2000-EXIT.
+    DISPLAY "D".
+UTIL SECTION.
+HELPER.
+    DISPLAY "E".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let proc_div = parse.root().unwrap().procedure_division().unwrap();
+        assert_eq!(proc_div.sentences().count(), 1);
+        let sections: Vec<_> = proc_div.sections().filter_map(|s| s.name()).collect();
+        assert_eq!(sections, vec!["MAIN-LOGIC", "UTIL"]);
+        let paragraphs: Vec<_> = proc_div.paragraphs().filter_map(|p| p.name()).collect();
+        assert_eq!(paragraphs, vec!["1000-INIT", "2000-EXIT", "HELPER"]);
+
+        let main_logic = proc_div.section("main-logic").unwrap();
+        assert_eq!(main_logic.sentences().count(), 1);
+        assert_eq!(main_logic.paragraphs().count(), 2);
+
+        let init = proc_div.paragraph("1000-INIT").unwrap();
+        assert_eq!(init.section().and_then(|s| s.name()), Some("MAIN-LOGIC".to_string()));
+        assert_eq!(init.sentences().count(), 2);
+        let texts: Vec<_> = init
+            .statements()
+            .filter_map(|stmt| match stmt.kind() {
+                StmtKind::Display(display) => display.string_literal(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts, vec!["A", "B", "C"]);
+        assert_eq!(proc_div.display_statements().count(), 7);
+    }
+
+    #[test]
+    fn test_move_statements() {
+        let source = r#"
+PROCEDURE DIVISION.
+    MOVE WS-A TO WS-B WS-C.
+    MOVE CORR IN-REC TO OUT-REC.
+    MOVE "ABC" TO WS-NAME(1:3).
+    MOVE ZEROS TO WS-TOTAL(WS-I, 2).
+    MOVE WS-LINE(WS-I)(WS-POS:) TO WS-OUT.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let moves: Vec<_> = parse
+            .syntax()
+            .descendants()
+            .filter_map(MoveStmt::cast)
+            .collect();
+        assert_eq!(moves.len(), 5);
+
+        let name = |r: &DataRef| r.name().unwrap();
+        let source_name = |m: &MoveStmt| m.source().and_then(|s| s.as_data_ref()).map(|r| name(&r));
+        assert_eq!(source_name(&moves[0]), Some("WS-A".to_string()));
+        assert_eq!(moves[0].targets().iter().map(name).collect::<Vec<_>>(), vec!["WS-B", "WS-C"]);
+        assert!(!moves[0].is_corresponding());
+
+        assert!(moves[1].is_corresponding());
+        assert_eq!(source_name(&moves[1]), Some("IN-REC".to_string()));
+
+        let literal = moves[2].source().and_then(|s| s.as_literal()).unwrap();
+        assert_eq!(literal.string_value(), Some("ABC".to_string()));
+        let ref_mod = moves[2].targets()[0].ref_mod().unwrap();
+        let number = |e: Option<Expr>| match e?.kind() {
+            ExprKind::Literal(l) => l.numeric_value(),
+            _ => None,
+        };
+        assert_eq!(number(ref_mod.start()), Some(NumericValue { value: 1, scale: 0 }));
+        assert_eq!(number(ref_mod.length()), Some(NumericValue { value: 3, scale: 0 }));
+
+        assert!(matches!(
+            moves[3].source().unwrap().kind(),
+            OperandKind::Literal(l) if l.figurative() == Some(Figurative::Zero)
+        ));
+        let subscripts = moves[3].targets()[0].subscripts();
+        assert_eq!(subscripts.len(), 2);
+        assert!(matches!(&subscripts[0], Subscript::Expr(e) if e.syntax().text() == "WS-I"));
+
+        let line = moves[4].source().and_then(|s| s.as_data_ref()).unwrap();
+        assert_eq!(line.subscripts().len(), 1);
+        let ref_mod = line.ref_mod().unwrap();
+        assert!(matches!(ref_mod.start().unwrap().kind(), ExprKind::DataRef(_)));
+        assert!(ref_mod.length().is_none());
+    }
+
+    #[test]
+    fn test_arithmetic_statements() {
+        let source = r#"
+PROCEDURE DIVISION.
+    ADD 1 TO WS-COUNT.
+    ADD WS-A WS-B TO WS-C GIVING WS-D ROUNDED
+        ON SIZE ERROR DISPLAY "OVERFLOW"
+        NOT ON SIZE ERROR MOVE 0 TO WS-FLAG
+    END-ADD.
+    SUBTRACT CORR WS-X FROM WS-Y.
+    MULTIPLY WS-RATE BY WS-HOURS ROUNDED MODE IS NEAREST-EVEN.
+    DIVIDE WS-TOTAL BY 7 GIVING WS-WEEKS REMAINDER WS-DAYS
+        SIZE ERROR DISPLAY "ZERO".
+    COMPUTE WS-R ROUNDED = (WS-A + 2) * -WS-B ** 2 - WS-C / 4
+        ON SIZE ERROR DISPLAY "BIG"
+    END-COMPUTE.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let stmts: Vec<_> = parse
+            .root()
+            .unwrap()
+            .procedure_division()
+            .unwrap()
+            .sentences()
+            .flat_map(|s| s.statements().collect::<Vec<_>>())
+            .collect();
+        assert_eq!(stmts.len(), 6);
+        let receiver_names = |receivers: Vec<Receiver>| -> Vec<String> {
+            receivers.iter().filter_map(|r| r.data_ref()?.name()).collect()
+        };
+
+        let StmtKind::Add(add) = stmts[0].kind() else { panic!("expected ADD") };
+        assert_eq!(add.operands().len(), 1);
+        assert_eq!(receiver_names(add.receivers()), vec!["WS-COUNT"]);
+
+        let StmtKind::Add(add) = stmts[1].kind() else { panic!("expected ADD") };
+        assert_eq!(add.operands().len(), 3);
+        assert_eq!(receiver_names(add.receivers()), vec!["WS-D"]);
+        assert!(add.receivers()[0].is_rounded());
+        assert_eq!(add.on_size_error().len(), 1);
+        assert!(matches!(add.not_on_size_error()[0].kind(), StmtKind::Move(_)));
+
+        let StmtKind::Subtract(subtract) = stmts[2].kind() else { panic!("expected SUBTRACT") };
+        assert!(subtract.is_corresponding());
+
+        let StmtKind::Multiply(multiply) = stmts[3].kind() else { panic!("expected MULTIPLY") };
+        assert_eq!(multiply.receivers()[0].rounded_mode(), Some("NEAREST-EVEN".to_string()));
+
+        let StmtKind::Divide(divide) = stmts[4].kind() else { panic!("expected DIVIDE") };
+        assert_eq!(divide.operands().len(), 2);
+        assert_eq!(receiver_names(divide.receivers()), vec!["WS-WEEKS"]);
+        assert_eq!(divide.remainder().and_then(|r| r.data_ref()?.name()), Some("WS-DAYS".to_string()));
+        assert_eq!(divide.on_size_error().len(), 1);
+
+        let StmtKind::Compute(compute) = stmts[5].kind() else { panic!("expected COMPUTE") };
+        assert!(compute.receivers()[0].is_rounded());
+        assert_eq!(compute.on_size_error().len(), 1);
+        // ((WS-A + 2) * ((-WS-B) ** 2)) - (WS-C / 4)
+        let ExprKind::Binary(minus) = compute.expr().unwrap().kind() else { panic!("expected -") };
+        assert_eq!(minus.op(), Some(BinaryOp::Subtract));
+        let ExprKind::Binary(times) = minus.lhs().unwrap().kind() else { panic!("expected *") };
+        assert_eq!(times.op(), Some(BinaryOp::Multiply));
+        assert!(matches!(times.lhs().unwrap().kind(), ExprKind::Paren(_)));
+        let ExprKind::Binary(power) = times.rhs().unwrap().kind() else { panic!("expected **") };
+        assert_eq!(power.op(), Some(BinaryOp::Power));
+        let ExprKind::Prefix(negation) = power.lhs().unwrap().kind() else { panic!("expected -") };
+        assert!(negation.is_negation());
+        let ExprKind::Binary(divide) = minus.rhs().unwrap().kind() else { panic!("expected /") };
+        assert_eq!(divide.op(), Some(BinaryOp::Divide));
     }
-}
 
-impl DisplayStmt {
-    pub fn string_literal(&self) -> Option<String> {
-        self.0
-            .children_with_tokens()
-            .filter_map(|el| el.into_token())
-            .find(|t| t.kind() == STRING_LITERAL)
-            .map(|t| {
-                let text = t.text();
-                // Remove quotes
-                text[1..text.len() - 1].to_string()
+    #[test]
+    fn test_if_statements() {
+        let source = r#"
+PROCEDURE DIVISION.
+    IF WS-A = 1 OR 2 AND > WS-B
+        DISPLAY "MATCH"
+    ELSE
+        IF WS-EOF DISPLAY "EOF" END-IF
+        MOVE 1 TO WS-C
+    END-IF.
+    IF WS-NAME IS NOT NUMERIC AND WS-AMT IS NEGATIVE
+        DISPLAY "BAD".
+    IF NOT (WS-X GREATER THAN OR EQUAL TO 10 OR WS-Y < 0)
+        THEN DISPLAY "IN RANGE".
+    IF (WS-A + 1) * 2 NOT = WS-B DISPLAY "DIFF".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let ifs: Vec<_> = parse
+            .root()
+            .unwrap()
+            .procedure_division()
+            .unwrap()
+            .sentences()
+            .flat_map(|s| s.statements().collect::<Vec<_>>())
+            .filter_map(|s| IfStmt::cast(s.syntax().clone()))
+            .collect();
+        assert_eq!(ifs.len(), 4);
+
+        // WS-A = 1 OR (2 AND > WS-B)
+        let first = &ifs[0];
+        assert_eq!(first.then_statements().len(), 1);
+        assert_eq!(first.else_statements().len(), 2);
+        let ConditionKind::Combined(or) = first.condition().unwrap().kind() else { panic!("expected OR") };
+        assert_eq!(or.op(), Some(LogicalOp::Or));
+        let ConditionKind::Combined(and) = or.rhs().unwrap().kind() else { panic!("expected AND") };
+        assert_eq!(and.op(), Some(LogicalOp::And));
+        let ConditionKind::Relation(two) = and.lhs().unwrap().kind() else { panic!("expected relation") };
+        assert!(two.is_abbreviated());
+        assert_eq!(two.effective_subject().unwrap().syntax().text().to_string(), "WS-A");
+        assert_eq!(two.effective_op().map(|op| op.comparison), Some(Comparison::Equal));
+        assert_eq!(two.object().unwrap().syntax().text().to_string(), "2");
+        let ConditionKind::Relation(greater) = and.rhs().unwrap().kind() else { panic!("expected relation") };
+        assert_eq!(greater.effective_subject().unwrap().syntax().text().to_string(), "WS-A");
+        assert_eq!(greater.op().map(|op| op.comparison), Some(Comparison::Greater));
+        let StmtKind::If(nested) = first.else_statements()[0].kind() else { panic!("expected IF") };
+        assert!(matches!(nested.condition().unwrap().kind(), ConditionKind::ConditionName(_)));
+
+        let ConditionKind::Combined(and) = ifs[1].condition().unwrap().kind() else { panic!("expected AND") };
+        let ConditionKind::Class(class) = and.lhs().unwrap().kind() else { panic!("expected class") };
+        assert_eq!(class.class(), Some(ClassName::Numeric));
+        assert!(class.is_negated());
+        let ConditionKind::Sign(sign) = and.rhs().unwrap().kind() else { panic!("expected sign") };
+        assert_eq!(sign.sign(), Some(Sign::Negative));
+
+        let ConditionKind::Not(not) = ifs[2].condition().unwrap().kind() else { panic!("expected NOT") };
+        let ConditionKind::Paren(paren) = not.condition().unwrap().kind() else { panic!("expected ()") };
+        let ConditionKind::Combined(or) = paren.condition().unwrap().kind() else { panic!("expected OR") };
+        let ConditionKind::Relation(at_least) = or.lhs().unwrap().kind() else { panic!("expected relation") };
+        assert_eq!(
+            at_least.op(),
+            Some(RelationalOp { comparison: Comparison::GreaterOrEqual, negated: false })
+        );
+
+        let ConditionKind::Relation(relation) = ifs[3].condition().unwrap().kind() else { panic!("expected relation") };
+        assert!(matches!(relation.subject().unwrap().kind(), ExprKind::Binary(_)));
+        assert_eq!(relation.op(), Some(RelationalOp { comparison: Comparison::Equal, negated: true }));
+    }
+
+    #[test]
+    fn test_condition_names_after_relations() {
+        let source = r#"
+DATA DIVISION.
+WORKING-STORAGE SECTION.
+01 WS-STATUS PIC X.
+   88 WS-EOF VALUE "Y".
+PROCEDURE DIVISION.
+    IF WS-A = 1 AND WS-EOF DISPLAY "DONE".
+    IF WS-A = 1 OR NOT ws-eof DISPLAY "MORE".
+    IF WS-A = 1 OR WS-B DISPLAY "EITHER".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+
+        let conditions: Vec<_> = parse
+            .syntax()
+            .descendants()
+            .filter_map(IfStmt::cast)
+            .map(|stmt| match stmt.condition().unwrap().kind() {
+                ConditionKind::Combined(combined) => combined.rhs().unwrap(),
+                _ => panic!("expected AND or OR"),
             })
+            .collect();
+        assert!(matches!(conditions[0].kind(), ConditionKind::ConditionName(_)));
+        let ConditionKind::Not(not) = conditions[1].kind() else { panic!("expected NOT") };
+        assert!(matches!(not.condition().unwrap().kind(), ConditionKind::ConditionName(_)));
+        // WS-B is not a condition name, so this is WS-A = 1 OR WS-A = WS-B
+        let ConditionKind::Relation(relation) = conditions[2].kind() else { panic!("expected relation") };
+        assert!(relation.is_abbreviated());
     }
-}
 
-impl Parse {
-    pub fn root(&self) -> Option<Root> {
-        Root::cast(self.syntax())
+    #[test]
+    fn test_evaluate_statements() {
+        let source = r#"
+PROCEDURE DIVISION.
+    EVALUATE WS-CODE ALSO TRUE
+        WHEN 1 THRU 5 ALSO WS-AMT > 100
+            DISPLAY "LOW BIG"
+        WHEN 6
+        WHEN NOT 7 ALSO ANY
+            DISPLAY "OTHERS"
+        WHEN OTHER
+            DISPLAY "NONE"
+    END-EVALUATE.
+    EVALUATE WS-A + 1 > WS-B
+        WHEN FALSE MOVE 0 TO WS-C.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let evaluates: Vec<_> = parse.syntax().descendants().filter_map(EvaluateStmt::cast).collect();
+        assert_eq!(evaluates.len(), 2);
+
+        let first = &evaluates[0];
+        let subjects: Vec<_> = first.subjects().iter().filter_map(|s| s.selection()).collect();
+        assert!(matches!(&subjects[0], Selection::Value { negated: false, thru: None, .. }));
+        assert_eq!(subjects[1], Selection::True);
+
+        let branches = first.branches();
+        assert_eq!(branches.len(), 2);
+        let alternatives = branches[0].alternatives();
+        assert_eq!(alternatives.len(), 1);
+        let Some(Selection::Value { value, thru: Some(thru), .. }) = alternatives[0][0].selection() else {
+            panic!("expected a range")
+        };
+        assert_eq!((value.syntax().text().to_string(), thru.syntax().text().to_string()), ("1".into(), "5".into()));
+        assert!(matches!(alternatives[0][1].selection(), Some(Selection::Condition(_))));
+        assert_eq!(branches[0].statements().len(), 1);
+
+        let alternatives = branches[1].alternatives();
+        assert_eq!(alternatives.len(), 2);
+        assert!(matches!(alternatives[1][0].selection(), Some(Selection::Value { negated: true, .. })));
+        assert_eq!(alternatives[1][1].selection(), Some(Selection::Any));
+        assert!(first.has_when_other());
+        assert_eq!(first.when_other_statements().len(), 1);
+
+        let second = &evaluates[1];
+        assert!(matches!(second.subjects()[0].selection(), Some(Selection::Condition(_))));
+        assert_eq!(second.branches()[0].alternatives()[0][0].selection(), Some(Selection::False));
+        assert!(!second.has_when_other());
     }
-}
 
-// ============================================================================
-// Tests
-// ============================================================================
+    #[test]
+    fn test_perform_statements() {
+        let source = r#"
+PROCEDURE DIVISION.
+    PERFORM 1000-INIT.
+    PERFORM 2000-READ THRU 2000-EXIT WS-N TIMES.
+    PERFORM 3000-LOOP WITH TEST AFTER UNTIL WS-EOF.
+    PERFORM 4000-CELL VARYING WS-I FROM 1 BY 1 UNTIL WS-I > 10
+        AFTER WS-J FROM 1 BY 2 UNTIL WS-J > 5.
+    PERFORM 3 TIMES
+        DISPLAY "HI"
+    END-PERFORM.
+    PERFORM VARYING WS-K FROM WS-START BY -1 UNTIL WS-K < 1
+        DISPLAY "K"
+        ADD 1 TO WS-COUNT
+    END-PERFORM.
+"#;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let performs: Vec<_> = parse.syntax().descendants().filter_map(PerformStmt::cast).collect();
+        assert_eq!(performs.len(), 6);
+
+        let range = performs[0].procedure().unwrap();
+        assert_eq!((range.start(), range.end()), (Some("1000-INIT".to_string()), None));
+        assert!(!performs[0].is_inline());
+
+        let range = performs[1].procedure().unwrap();
+        assert_eq!(range.end(), Some("2000-EXIT".to_string()));
+        assert!(performs[1].times().unwrap().as_data_ref().is_some());
+
+        assert!(performs[2].is_test_after());
+        assert!(matches!(performs[2].until().unwrap().kind(), ConditionKind::ConditionName(_)));
+
+        let varying = performs[3].varying();
+        assert_eq!(varying.len(), 2);
+        assert_eq!(varying[1].counter().and_then(|c| c.name()), Some("WS-J".to_string()));
+        assert_eq!(varying[1].by().unwrap().syntax().text().to_string(), "2");
+        assert!(varying[1].until().is_some());
+
+        assert!(performs[4].is_inline());
+        assert_eq!(performs[4].times().unwrap().syntax().text().to_string(), "3");
+        assert_eq!(performs[4].statements().len(), 1);
+
+        assert!(performs[5].is_inline() && !performs[5].is_test_after());
+        let varying = performs[5].varying();
+        assert_eq!(varying[0].from().unwrap().syntax().text().to_string(), "WS-START");
+        assert_eq!(varying[0].by().unwrap().syntax().text().to_string(), "-1");
+        assert_eq!(performs[5].statements().len(), 2);
+    }
 
     #[test]
-    fn test_parse_simple_cobol() {
+    fn test_control_transfer_statements() {
         let source = r#"
-IDENTIFICATION DIVISION.
-PROGRAM-ID. HELLO.
 PROCEDURE DIVISION.
-    DISPLAY "Hello, World!".
+MAIN-PARA.
+    GO TO READ-PARA.
+READ-PARA.
+    GO TO P1 P2 P3 DEPENDING ON WS-CHOICE.
+P1.
+    IF WS-A > 1 NEXT SENTENCE ELSE CONTINUE END-IF.
+    EXIT PERFORM CYCLE.
+P2.
+    ALTER SWITCH-PARA TO PROCEED TO P3.
+    STOP "PAUSED".
+P3.
+    EXIT PARAGRAPH.
+SWITCH-PARA.
+    GO.
+DONE-PARA.
+    EXIT.
+    GOBACK.
+    STOP RUN.
 "#;
 
         let parse = parse(source);
         assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+        assert_eq!(parse.warnings, vec!["ALTER changes the GO TO in SWITCH-PARA at run time".to_string()]);
 
-        let root = parse.root().expect("Should have root");
+        let proc_div = parse.root().unwrap().procedure_division().unwrap();
+        let stmts = |name: &str| proc_div.paragraph(name).unwrap().statements().collect::<Vec<_>>();
 
-        // Check IDENTIFICATION DIVISION
-        assert!(root.identification_division().is_some());
+        let StmtKind::GoTo(go_to) = stmts("READ-PARA")[0].kind() else { panic!("expected GO TO") };
+        assert_eq!(go_to.targets(), vec!["P1", "P2", "P3"]);
+        assert_eq!(go_to.depending_on().and_then(|d| d.name()), Some("WS-CHOICE".to_string()));
 
-        // Check PROGRAM-ID
-        let program_id = root.program_id().expect("Should have PROGRAM-ID");
-        assert_eq!(program_id.name(), Some("HELLO".to_string()));
+        let p1 = stmts("P1");
+        let StmtKind::If(if_stmt) = p1[0].kind() else { panic!("expected IF") };
+        assert!(matches!(if_stmt.then_statements()[0].kind(), StmtKind::NextSentence(_)));
+        assert!(matches!(if_stmt.else_statements()[0].kind(), StmtKind::Continue(_)));
+        let StmtKind::Exit(exit) = p1[1].kind() else { panic!("expected EXIT") };
+        assert_eq!(exit.kind(), ExitKind::PerformCycle);
 
-        // Check PROCEDURE DIVISION
-        let proc_div = root.procedure_division().expect("Should have PROCEDURE DIVISION");
-        let displays: Vec<_> = proc_div.display_statements().collect();
-        assert_eq!(displays.len(), 1);
-        assert_eq!(displays[0].string_literal(), Some("Hello, World!".to_string()));
+        let p2 = stmts("P2");
+        let StmtKind::Alter(alter) = p2[0].kind() else { panic!("expected ALTER") };
+        assert_eq!(alter.alterations(), vec![("SWITCH-PARA".to_string(), "P3".to_string())]);
+        assert!(p2[1].falls_through());
+
+        let StmtKind::GoTo(altered) = stmts("SWITCH-PARA")[0].kind() else { panic!("expected GO TO") };
+        assert!(altered.targets().is_empty());
+
+        let done: Vec<_> = stmts("DONE-PARA").iter().map(|s| s.falls_through()).collect();
+        assert_eq!(done, vec![true, false, false]);
+        assert!(!stmts("P3")[0].falls_through());
     }
 
     #[test]
-    fn test_parse_multiple_display() {
+    fn test_string_handling_statements() {
         let source = r#"
-IDENTIFICATION DIVISION.
-PROGRAM-ID. MULTI.
 PROCEDURE DIVISION.
-    DISPLAY "First".
-    DISPLAY "Second".
-    DISPLAY "Third".
+    STRING WS-FIRST " " WS-LAST DELIMITED BY SIZE
+           WS-CITY DELIMITED BY ","
+        INTO WS-LINE WITH POINTER WS-PTR
+        ON OVERFLOW DISPLAY "TOO LONG"
+    END-STRING.
+    UNSTRING WS-CSV DELIMITED BY ALL SPACES OR ","
+        INTO WS-F1 DELIMITER IN WS-D1 COUNT IN WS-C1
+             WS-F2
+        TALLYING IN WS-FIELDS
+        NOT ON OVERFLOW CONTINUE
+    END-UNSTRING.
+    INSPECT WS-TEXT TALLYING WS-A FOR ALL "A" "B" LEADING SPACES
+                             WS-N FOR CHARACTERS BEFORE INITIAL "."
+        REPLACING ALL "A" BY "X" AFTER "*" FIRST "B" BY "Y".
+    INSPECT WS-TEXT CONVERTING "abc" TO "ABC" BEFORE ";".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+        let text = |node: &SyntaxNode| node.text().to_string();
+
+        let string = parse.syntax().descendants().find_map(StringStmt::cast).unwrap();
+        let sources = string.sources();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].values().len(), 3);
+        assert!(sources[0].is_delimited_by_size());
+        assert_eq!(text(sources[1].delimiter().unwrap().syntax()), "\",\"");
+        assert_eq!(string.target().and_then(|t| t.name()), Some("WS-LINE".to_string()));
+        assert_eq!(string.pointer().and_then(|t| t.name()), Some("WS-PTR".to_string()));
+        assert_eq!(string.on_overflow().len(), 1);
+
+        let unstring = parse.syntax().descendants().find_map(UnstringStmt::cast).unwrap();
+        assert_eq!(unstring.source().and_then(|s| s.name()), Some("WS-CSV".to_string()));
+        let delimiters = unstring.delimiters();
+        assert_eq!(delimiters.len(), 2);
+        assert!(delimiters[0].is_all() && !delimiters[1].is_all());
+        let receivers = unstring.receivers();
+        assert_eq!(receivers.len(), 2);
+        assert_eq!(receivers[0].delimiter_in().and_then(|d| d.name()), Some("WS-D1".to_string()));
+        assert_eq!(receivers[0].count_in().and_then(|d| d.name()), Some("WS-C1".to_string()));
+        assert!(receivers[1].count_in().is_none());
+        assert_eq!(unstring.tallying().and_then(|d| d.name()), Some("WS-FIELDS".to_string()));
+        assert_eq!(unstring.not_on_overflow().len(), 1);
+
+        let inspects: Vec<_> = parse.syntax().descendants().filter_map(InspectStmt::cast).collect();
+        let tallying = inspects[0].tallying();
+        assert_eq!(tallying.len(), 2);
+        let groups = tallying[0].groups();
+        assert_eq!(groups.iter().map(|g| g.mode().unwrap()).collect::<Vec<_>>(), vec![InspectMode::All, InspectMode::Leading]);
+        assert_eq!(groups[0].items().len(), 2);
+        let characters = &tallying[1].groups()[0];
+        assert_eq!(characters.mode(), Some(InspectMode::Characters));
+        let boundary = &characters.items()[0].boundaries()[0];
+        assert!(boundary.is_before() && boundary.is_initial());
+        assert!(characters.items()[0].pattern().is_none());
+
+        let replacing = inspects[0].replacing();
+        assert_eq!(replacing.len(), 2);
+        let item = &replacing[0].items()[0];
+        assert_eq!(text(item.pattern().unwrap().syntax()), "\"A\"");
+        assert_eq!(text(item.replacement().unwrap().syntax()), "\"X\"");
+        assert!(!item.boundaries()[0].is_before());
+        assert_eq!(replacing[1].mode(), Some(InspectMode::First));
+
+        let converting = inspects[1].converting().unwrap();
+        assert_eq!(text(converting.from().unwrap().syntax()), "\"abc\"");
+        assert_eq!(text(converting.to().unwrap().syntax()), "\"ABC\"");
+        assert_eq!(converting.boundaries().len(), 1);
+    }
+
+    #[test]
+    fn test_file_io_statements() {
+        let source = r#"
+ENVIRONMENT DIVISION.
+INPUT-OUTPUT SECTION.
+FILE-CONTROL.
+    SELECT CUST-FILE ASSIGN TO "CUSTMAST"
+        ORGANIZATION IS INDEXED ACCESS MODE IS DYNAMIC
+        RECORD KEY IS CUST-ID.
+    SELECT REPORT-FILE ASSIGN TO "RPTOUT".
+DATA DIVISION.
+FILE SECTION.
+FD CUST-FILE.
+01 CUST-REC.
+   05 CUST-ID PIC X(6).
+FD REPORT-FILE.
+01 REPORT-LINE PIC X(132).
+WORKING-STORAGE SECTION.
+01 WS-CUST PIC X(6).
+PROCEDURE DIVISION.
+    OPEN I-O CUST-FILE OUTPUT REPORT-FILE.
+    READ CUST-FILE NEXT RECORD INTO WS-CUST
+        AT END MOVE "Y" TO WS-EOF
+        NOT AT END ADD 1 TO WS-COUNT
+    END-READ.
+    READ CUST-FILE KEY IS CUST-ID
+        INVALID KEY DISPLAY "MISSING".
+    START CUST-FILE KEY IS NOT LESS THAN CUST-ID
+        INVALID KEY CONTINUE
+    END-START.
+    REWRITE CUST-REC FROM WS-CUST INVALID DISPLAY "FAILED".
+    DELETE CUST-FILE RECORD.
+    WRITE REPORT-LINE FROM WS-CUST AFTER ADVANCING 2 LINES
+        AT EOP PERFORM NEW-PAGE.
+    WRITE REPORT-LINE BEFORE PAGE.
+    CLOSE CUST-FILE REPORT-FILE WITH LOCK.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let stmts: Vec<_> = parse
+            .root()
+            .unwrap()
+            .procedure_division()
+            .unwrap()
+            .sentences()
+            .flat_map(|s| s.statements().collect::<Vec<_>>())
+            .collect();
+        assert_eq!(stmts.len(), 9);
+
+        // Every statement is linked to its SELECT entry and FD
+        let inventory: Vec<_> = stmts
+            .iter()
+            .flat_map(|s| s.files())
+            .map(|f| (f.name.clone(), f.select.and_then(|s| s.assign_to()), f.fd.is_some()))
+            .collect();
+        let cust = ("CUST-FILE".to_string(), Some("CUSTMAST".to_string()), true);
+        let report = ("REPORT-FILE".to_string(), Some("RPTOUT".to_string()), true);
+        assert_eq!(
+            inventory,
+            vec![
+                cust.clone(),
+                report.clone(),
+                cust.clone(),
+                cust.clone(),
+                cust.clone(),
+                cust.clone(),
+                cust.clone(),
+                report.clone(),
+                report.clone(),
+                cust,
+                report,
+            ]
+        );
+
+        let StmtKind::Open(open) = stmts[0].kind() else { panic!("expected OPEN") };
+        assert_eq!(
+            open.files(),
+            vec![(OpenMode::InputOutput, "CUST-FILE".to_string()), (OpenMode::Output, "REPORT-FILE".to_string())]
+        );
+
+        let StmtKind::Read(read) = stmts[1].kind() else { panic!("expected READ") };
+        assert!(read.is_next());
+        assert_eq!(read.target().and_then(|i| i.name()), Some("WS-CUST".to_string()));
+        assert_eq!((read.at_end().len(), read.not_at_end().len()), (1, 1));
+
+        let StmtKind::Read(read) = stmts[2].kind() else { panic!("expected READ") };
+        assert_eq!(read.key().and_then(|k| k.name()), Some("CUST-ID".to_string()));
+        assert_eq!(read.invalid_key().len(), 1);
+
+        let StmtKind::Start(start) = stmts[3].kind() else { panic!("expected START") };
+        assert_eq!(start.key_op(), RelationalOp { comparison: Comparison::Less, negated: true });
+
+        let StmtKind::Rewrite(rewrite) = stmts[4].kind() else { panic!("expected REWRITE") };
+        assert_eq!(rewrite.record_name(), Some("CUST-REC".to_string()));
+        assert_eq!(rewrite.invalid_key().len(), 1);
+
+        let StmtKind::Write(write) = stmts[6].kind() else { panic!("expected WRITE") };
+        let advancing = write.advancing().unwrap();
+        assert!(!advancing.is_before() && !advancing.is_page());
+        assert_eq!(advancing.lines().unwrap().syntax().text().to_string(), "2");
+        assert_eq!(write.at_end_of_page().len(), 1);
+        let StmtKind::Write(write) = stmts[7].kind() else { panic!("expected WRITE") };
+        assert!(write.advancing().unwrap().is_page());
+
+        let StmtKind::Close(close) = stmts[8].kind() else { panic!("expected CLOSE") };
+        assert_eq!(close.files(), vec!["CUST-FILE", "REPORT-FILE"]);
+
+        // A START without KEY positions on an equal key; a record that is
+        // not in an FD names no file
+        let parse = super::parse("PROCEDURE DIVISION.\n    START CUST-FILE.\n    WRITE NO-SUCH-REC.\n");
+        let stmts: Vec<_> = parse.syntax().descendants().filter_map(Stmt::cast).collect();
+        let StmtKind::Start(start) = stmts[0].kind() else { panic!("expected START") };
+        assert_eq!(start.key_op(), RelationalOp { comparison: Comparison::Equal, negated: false });
+        assert!(stmts[1].files().is_empty());
+    }
+
+    #[test]
+    fn test_call_statements() {
+        let source = r#"
+PROCEDURE DIVISION USING LK-INPUT BY VALUE LK-LENGTH RETURNING LK-RESULT.
+    CALL "DATEUTIL" USING BY REFERENCE WS-DATE
+        BY CONTENT "YYYYMMDD" WS-FORMAT
+        BY VALUE WS-LEN OMITTED
+        RETURNING WS-RC
+        ON EXCEPTION DISPLAY "DATEUTIL NOT FOUND"
+        NOT ON EXCEPTION ADD 1 TO WS-CALLS
+    END-CALL.
+    CALL WS-PROGRAM USING WS-AREA ON OVERFLOW CONTINUE.
+    CANCEL "DATEUTIL" WS-PROGRAM.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let procedure = parse.root().unwrap().procedure_division().unwrap();
+        let name = |arg: &Argument| arg.value.as_ref().map(|v| v.syntax().text().to_string());
+        let using: Vec<_> = procedure.using().iter().map(|a| (a.mode, name(a))).collect();
+        assert_eq!(
+            using,
+            vec![
+                (PassingMode::Reference, Some("LK-INPUT".to_string())),
+                (PassingMode::Value, Some("LK-LENGTH".to_string())),
+            ]
+        );
+        assert_eq!(procedure.returning().and_then(|r| r.name()), Some("LK-RESULT".to_string()));
+
+        let calls: Vec<_> = procedure.call_statements().collect();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].target(), Some(CallTarget::Static("DATEUTIL".to_string())));
+        let arguments: Vec<_> = calls[0].arguments().iter().map(|a| (a.mode, name(a))).collect();
+        assert_eq!(
+            arguments,
+            vec![
+                (PassingMode::Reference, Some("WS-DATE".to_string())),
+                (PassingMode::Content, Some("\"YYYYMMDD\"".to_string())),
+                (PassingMode::Content, Some("WS-FORMAT".to_string())),
+                (PassingMode::Value, Some("WS-LEN".to_string())),
+                (PassingMode::Value, None),
+            ]
+        );
+        assert_eq!(calls[0].returning().and_then(|r| r.name()), Some("WS-RC".to_string()));
+        assert_eq!(calls[0].on_exception().len(), 1);
+        assert_eq!(calls[0].not_on_exception().len(), 1);
+
+        let Some(CallTarget::Dynamic(target)) = calls[1].target() else { panic!("expected dynamic call") };
+        assert_eq!(target.name(), Some("WS-PROGRAM".to_string()));
+        assert_eq!(calls[1].on_exception().len(), 1);
+
+        let cancel = procedure.sentences().nth(2).unwrap().statements().next().unwrap();
+        let StmtKind::Cancel(cancel) = cancel.kind() else { panic!("expected CANCEL") };
+        assert!(matches!(
+            cancel.targets().as_slice(),
+            [CallTarget::Static(name), CallTarget::Dynamic(_)] if name == "DATEUTIL"
+        ));
+    }
+
+    #[test]
+    fn test_display_and_accept_statements() {
+        let source = r#"
+PROCEDURE DIVISION.
+    DISPLAY "TOTAL: " WS-TOTAL(WS-IX), " ITEMS" UPON CONSOLE WITH NO ADVANCING.
+    DISPLAY WS-NAME.
+    ACCEPT WS-REPLY.
+    ACCEPT WS-DATE FROM DATE YYYYMMDD.
+    ACCEPT WS-DAY FROM DAY.
+    ACCEPT YYYYMMDD FROM DAY yyyyddd.
+    MOVE YYYYMMDD TO WS-DATE.
+    ACCEPT WS-TIME FROM TIME.
+    ACCEPT WS-REPLY FROM CONSOLE.
+    ACCEPT WS-HOME FROM ENVIRONMENT "HOME".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let procedure = parse.root().unwrap().procedure_division().unwrap();
+        let displays: Vec<_> = procedure.display_statements().collect();
+        let operands: Vec<_> = displays[0].operands().iter().map(|o| o.syntax().text().to_string()).collect();
+        assert_eq!(operands, vec!["\"TOTAL: \"", "WS-TOTAL(WS-IX)", "\" ITEMS\""]);
+        assert_eq!(displays[0].string_literal(), Some("TOTAL: ".to_string()));
+        assert_eq!(displays[0].upon(), Some("CONSOLE".to_string()));
+        assert!(displays[0].is_no_advancing());
+        assert_eq!(displays[1].operands()[0].as_data_ref().and_then(|d| d.name()), Some("WS-NAME".to_string()));
+        assert!(displays[1].upon().is_none() && !displays[1].is_no_advancing());
+
+        let accepts: Vec<_> = procedure.syntax().descendants().filter_map(AcceptStmt::cast).collect();
+        assert_eq!(accepts[0].target().and_then(|t| t.name()), Some("WS-REPLY".to_string()));
+        // YYYYMMDD and YYYYDDD are context words and can name data items
+        assert_eq!(accepts[3].target().and_then(|t| t.name()), Some("YYYYMMDD".to_string()));
+        let sources: Vec<_> = accepts.iter().map(|a| a.source()).collect();
+        assert_eq!(
+            sources[..6],
+            [
+                AcceptSource::Input,
+                AcceptSource::Date { four_digit_year: true },
+                AcceptSource::Day { four_digit_year: false },
+                AcceptSource::Day { four_digit_year: true },
+                AcceptSource::Time,
+                AcceptSource::Device("CONSOLE".to_string()),
+            ]
+        );
+        let AcceptSource::Environment(name) = &sources[6] else { panic!("expected ENVIRONMENT") };
+        assert_eq!(name.as_literal().and_then(|l| l.string_value()), Some("HOME".to_string()));
+    }
+
+    #[test]
+    fn test_table_handling_statements() {
+        let source = r#"
+DATA DIVISION.
+WORKING-STORAGE SECTION.
+01 WS-TABLE.
+   05 WS-ENTRY OCCURS 50 TIMES ASCENDING KEY IS WS-CODE INDEXED BY WS-IX WS-JX.
+      10 WS-CODE PIC X(4).
+      10 WS-RATE PIC 9V99.
+01 WS-STATUS PIC X.
+   88 WS-FOUND VALUE "Y".
+01 WS-PTR USAGE POINTER.
+LINKAGE SECTION.
+01 LK-AREA PIC X(100).
+PROCEDURE DIVISION.
+    SET WS-IX TO 1.
+    SEARCH WS-ENTRY VARYING WS-JX
+        AT END DISPLAY "NOT FOUND"
+        WHEN WS-CODE(WS-JX) = "ABCD" SET WS-FOUND TO TRUE
+        WHEN WS-RATE(WS-JX) > 1 NEXT SENTENCE
+    END-SEARCH.
+    SEARCH ALL WS-ENTRY
+        WHEN WS-CODE(WS-IX) = WS-KEY MOVE WS-RATE(WS-IX) TO WS-OUT.
+    SET WS-IX WS-JX UP BY 2.
+    SET WS-IX DOWN BY WS-STEP.
+    SET ADDRESS OF LK-AREA TO WS-PTR.
+    SET WS-PTR TO ADDRESS OF LK-AREA.
+    SET WS-FOUND TO FALSE.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let procedure = parse.root().unwrap().procedure_division().unwrap();
+        let searches: Vec<_> = procedure.syntax().descendants().filter_map(SearchStmt::cast).collect();
+        assert_eq!(searches.len(), 2);
+        assert!(!searches[0].is_all());
+        assert_eq!(searches[0].table_item().and_then(|t| t.occurs_clause()?.max()), Some(50));
+        assert_eq!(searches[0].index(), Some("WS-JX".to_string()));
+        assert_eq!(searches[0].at_end().len(), 1);
+        let whens: Vec<_> = searches[0].whens().collect();
+        assert_eq!(whens.len(), 2);
+        assert!(whens[0].condition().is_some());
+        assert!(matches!(whens[0].statements()[0].kind(), StmtKind::Set(_)));
+        assert!(matches!(whens[1].statements()[0].kind(), StmtKind::NextSentence(_)));
+        assert!(searches[1].is_all());
+        assert_eq!(searches[1].index(), Some("WS-IX".to_string()));
+        assert!(searches[1].at_end().is_empty());
+
+        let invalid = super::parse(
+            "PROCEDURE DIVISION.\n    SEARCH ALL WS-ENTRY VARYING WS-JX\n        \
+             WHEN WS-CODE(WS-IX) = \"A\" CONTINUE\n        WHEN WS-RATE(WS-IX) > 1 CONTINUE.\n    \
+             SEARCH ALL WS-ENTRY WHEN WS-CODE(WS-IX) = \"A\" OR NOT WS-FOUND CONTINUE.\n",
+        );
+        let conditions = "SEARCH ALL conditions must be EQUAL relations or condition-names joined by AND";
+        assert_eq!(
+            invalid.errors,
+            vec![
+                "VARYING is not allowed in SEARCH ALL",
+                "SEARCH ALL takes a single WHEN phrase",
+                conditions,
+                conditions,
+            ]
+        );
+        let valid = super::parse(
+            "PROCEDURE DIVISION.\n    SEARCH ALL WS-ENTRY\n        \
+             WHEN WS-CODE(WS-IX) EQUAL TO \"A\" AND WS-FOUND CONTINUE.\n",
+        );
+        assert!(valid.errors.is_empty(), "Errors: {:?}", valid.errors);
+
+        let sets: Vec<_> = procedure.syntax().descendants().filter_map(SetStmt::cast).collect();
+        assert_eq!(sets.len(), 7);
+        let literal = |action: Option<SetAction>| match action {
+            Some(SetAction::To(SetValue::Operand(o)) | SetAction::UpBy(o) | SetAction::DownBy(o)) => {
+                o.syntax().text().to_string()
+            }
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(literal(sets[0].action()), "1");
+        assert_eq!(sets[0].indexed_tables()[0].name(), Some("WS-ENTRY".to_string()));
+        assert_eq!(sets[1].action(), Some(SetAction::To(SetValue::True)));
+        assert!(sets[1].indexed_tables().is_empty());
+        assert_eq!(sets[2].targets().len(), 2);
+        assert!(matches!(sets[3].action(), Some(SetAction::DownBy(_))));
+        assert_eq!(literal(sets[3].action()), "WS-STEP");
+        assert!(matches!(
+            sets[4].targets().as_slice(),
+            [SetTarget::AddressOf(r)] if r.name().as_deref() == Some("LK-AREA")
+        ));
+        assert!(matches!(sets[5].action(), Some(SetAction::To(SetValue::AddressOf(_)))));
+        assert_eq!(sets[6].action(), Some(SetAction::To(SetValue::False)));
+    }
+
+    #[test]
+    fn test_sort_and_merge_statements() {
+        let source = r#"
+ENVIRONMENT DIVISION.
+INPUT-OUTPUT SECTION.
+FILE-CONTROL.
+    SELECT SORT-WORK ASSIGN TO "SORTWK1".
+    SELECT TRANS-IN ASSIGN TO "TRANSIN".
+    SELECT TRANS-OUT ASSIGN TO "TRANSOUT".
+DATA DIVISION.
+FILE SECTION.
+SD SORT-WORK.
+01 SORT-REC.
+   05 SR-BRANCH PIC X(4).
+   05 SR-AMOUNT PIC S9(7)V99.
+FD TRANS-IN.
+01 TRANS-REC PIC X(80).
+PROCEDURE DIVISION.
+    SORT SORT-WORK ON ASCENDING KEY SR-BRANCH
+        DESCENDING SR-AMOUNT
+        WITH DUPLICATES IN ORDER
+        INPUT PROCEDURE IS SELECT-RECORDS THRU SELECT-EXIT
+        GIVING TRANS-OUT.
+    MERGE SORT-WORK ON ASCENDING KEY SR-BRANCH
+        USING TRANS-IN TRANS-OUT
+        OUTPUT PROCEDURE PRINT-REPORT.
+    STOP RUN.
+SELECT-RECORDS.
+    RELEASE SORT-REC FROM TRANS-REC.
+SELECT-EXIT.
+    EXIT.
+PRINT-REPORT.
+    RETURN SORT-WORK INTO WS-LINE
+        AT END MOVE "Y" TO WS-EOF
+    END-RETURN.
 "#;
 
         let parse = parse(source);
         assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
 
         let root = parse.root().unwrap();
-        let proc_div = root.procedure_division().unwrap();
-        let displays: Vec<_> = proc_div.display_statements().collect();
+        let fds: Vec<_> = root.data_division().unwrap().file_section().unwrap().file_descriptions().collect();
+        assert!(fds[0].is_sort() && !fds[1].is_sort());
+        assert_eq!(fds[0].records().next().unwrap().name(), Some("SORT-REC".to_string()));
 
-        assert_eq!(displays.len(), 3);
-        assert_eq!(displays[0].string_literal(), Some("First".to_string()));
-        assert_eq!(displays[1].string_literal(), Some("Second".to_string()));
-        assert_eq!(displays[2].string_literal(), Some("Third".to_string()));
+        let procedure = root.procedure_division().unwrap();
+        let stmts: Vec<_> = procedure.syntax().descendants().filter_map(Stmt::cast).collect();
+
+        let StmtKind::Sort(sort) = stmts[0].kind() else { panic!("expected SORT") };
+        assert_eq!(sort.file_name(), Some("SORT-WORK".to_string()));
+        assert_eq!(
+            sort.keys(),
+            vec![
+                (KeyOrder::Ascending, "SR-BRANCH".to_string()),
+                (KeyOrder::Descending, "SR-AMOUNT".to_string()),
+            ]
+        );
+        assert!(sort.has_duplicates_in_order());
+        assert!(sort.using().is_empty());
+        assert_eq!(sort.giving(), vec!["TRANS-OUT"]);
+        let input = sort.input_procedure().unwrap();
+        assert_eq!(input.start(), Some("SELECT-RECORDS".to_string()));
+        assert_eq!(input.end(), Some("SELECT-EXIT".to_string()));
+        assert!(sort.output_procedure().is_none());
+        let names: Vec<_> = stmts[0].files().into_iter().map(|f| (f.name, f.fd.is_some())).collect();
+        assert_eq!(names, vec![("SORT-WORK".to_string(), true), ("TRANS-OUT".to_string(), false)]);
+
+        let StmtKind::Merge(merge) = stmts[1].kind() else { panic!("expected MERGE") };
+        assert_eq!(merge.using(), vec!["TRANS-IN", "TRANS-OUT"]);
+        assert_eq!(merge.output_procedure().and_then(|p| p.start()), Some("PRINT-REPORT".to_string()));
+        let invoked: Vec<_> = stmts[1].invoked_procedures().iter().filter_map(|p| p.start()).collect();
+        assert_eq!(invoked, vec!["PRINT-REPORT"]);
+
+        let release = stmts.iter().find(|s| matches!(s.kind(), StmtKind::Release(_))).unwrap();
+        assert_eq!(release.files()[0].name, "SORT-WORK");
+        let StmtKind::Release(release) = release.kind() else { unreachable!() };
+        assert_eq!(release.record_name(), Some("SORT-REC".to_string()));
+
+        let StmtKind::Return(ret) = stmts.iter().rev().nth(1).unwrap().kind() else { panic!("expected RETURN") };
+        assert_eq!(ret.file_name(), Some("SORT-WORK".to_string()));
+        assert_eq!(ret.target().and_then(|t| t.name()), Some("WS-LINE".to_string()));
+        assert_eq!(ret.at_end().len(), 1);
+
+        let invalid = super::parse(
+            "PROCEDURE DIVISION.\n    MERGE SORT-WORK ON ASCENDING KEY SR-BRANCH WITH DUPLICATES\n        \
+             USING TRANS-IN GIVING TRANS-OUT.\n    MERGE SORT-WORK ON ASCENDING KEY SR-BRANCH\n        \
+             INPUT PROCEDURE SELECT-RECORDS GIVING TRANS-OUT.\n",
+        );
+        assert_eq!(
+            invalid.errors,
+            vec![
+                "WITH DUPLICATES is not allowed in MERGE",
+                "MERGE needs at least two USING files",
+                "INPUT PROCEDURE is not allowed in MERGE",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identifier_references() {
+        let source = r#"
+DATA DIVISION.
+FILE SECTION.
+FD CUST-FILE.
+01 CUST-REC.
+   05 CUST-AMT PIC 9(5).
+WORKING-STORAGE SECTION.
+01 WS-REC.
+   05 WS-LINE OCCURS 10 INDEXED BY WS-IX.
+      10 CUST-AMT PIC 9(5) OCCURS 4.
+01 WS-OUT PIC X(20).
+PROCEDURE DIVISION.
+    MOVE CUST-AMT OF WS-LINE IN WS-REC (WS-IX + 1, 3) (WS-POS - 1:2) TO WS-OUT.
+    MOVE CUST-AMT IN CUST-FILE TO WS-OUT (1:LENGTH-OF-OUT * 2).
+    COMPUTE WS-TOTAL = CUST-AMT (WS-IX - 2, ALL) + 1.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let data = root.data_division().unwrap();
+        let refs: Vec<_> = root
+            .procedure_division()
+            .unwrap()
+            .syntax()
+            .descendants()
+            .filter_map(DataRef::cast)
+            .filter(|r| r.name().as_deref() == Some("CUST-AMT"))
+            .collect();
+        assert_eq!(refs.len(), 3);
+
+        assert_eq!(refs[0].qualifiers(), vec!["WS-LINE", "WS-REC"]);
+        let subscripts = refs[0].subscripts();
+        assert_eq!(subscripts.len(), 2);
+        let (index, offset) = subscripts[0].relative().unwrap();
+        assert_eq!((index.name(), offset), (Some("WS-IX".to_string()), 1));
+        assert!(subscripts[1].relative().is_none());
+        let ref_mod = refs[0].ref_mod().unwrap();
+        assert!(matches!(ref_mod.start().unwrap().kind(), ExprKind::Binary(_)));
+        assert_eq!(ref_mod.length().unwrap().syntax().text().to_string(), "2");
+        let resolved = data.resolve(&refs[0]).unwrap();
+        assert_eq!(resolved.occurs_clause().and_then(|o| o.max()), Some(4));
+
+        assert_eq!(refs[1].qualifiers(), vec!["CUST-FILE"]);
+        assert!(data.resolve(&refs[1]).unwrap().occurs_clause().is_none());
+
+        let subscripts = refs[2].subscripts();
+        assert_eq!(subscripts[0].relative().map(|(_, offset)| offset), Some(-2));
+        assert_eq!(subscripts[1], Subscript::All);
+        assert!(refs[2].qualifiers().is_empty());
+
+        let out = root.procedure_division().unwrap().syntax().descendants().filter_map(DataRef::cast);
+        let out = out.filter(|r| r.name().as_deref() == Some("WS-OUT")).nth(1).unwrap();
+        assert!(matches!(out.ref_mod().unwrap().length().unwrap().kind(), ExprKind::Binary(_)));
+
+        // Record and data names outside expressions are qualified as well
+        let source = r#"
+DATA DIVISION.
+FILE SECTION.
+FD OUT-FILE.
+01 OUT-REC PIC X(80).
+WORKING-STORAGE SECTION.
+01 WS-COUNTS.
+   05 WS-COUNT PIC 99.
+01 WS-TOTALS.
+   05 WS-COUNT PIC 9(4).
+01 WS-TABLE.
+   05 WS-ROW OCCURS 1 TO 10 DEPENDING ON WS-COUNT OF WS-COUNTS
+      ASCENDING KEY IS WS-KEY OF WS-ROW.
+      10 WS-KEY PIC X(4).
+PROCEDURE DIVISION.
+    WRITE OUT-REC OF OUT-FILE FROM WS-TABLE.
+    REWRITE OUT-REC IN OUT-FILE.
+    MOVE WS-COUNT TO WS-COUNT OF WS-TOTALS.
+"#;
+        let parse = super::parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        let occurs = parse.syntax().descendants().find_map(OccursClause::cast).unwrap();
+        assert_eq!(occurs.depending_on(), Some("WS-COUNT".to_string()));
+        assert_eq!(occurs.keys(), vec![(KeyOrder::Ascending, "WS-KEY".to_string())]);
+        let stmts: Vec<_> = parse.syntax().descendants().filter_map(Stmt::cast).collect();
+        assert_eq!(stmts.len(), 3);
+        let StmtKind::Write(write) = stmts[0].kind() else { panic!("expected WRITE") };
+        assert_eq!(write.record_name(), Some("OUT-REC".to_string()));
+        assert!(write.from().is_some());
+        for stmt in &stmts[..2] {
+            let files = stmt.files();
+            assert_eq!(files[0].name, "OUT-FILE");
+            assert!(files[0].fd.is_some());
+        }
+
+        let data = parse.root().unwrap().data_division().unwrap();
+        let StmtKind::Move(move_stmt) = stmts[2].kind() else { panic!("expected MOVE") };
+        let source = move_stmt.source().and_then(|s| s.as_data_ref()).unwrap();
+        let error = data.resolve(&source).unwrap_err();
+        assert_eq!(error, ResolveError::Ambiguous { name: "WS-COUNT".to_string(), candidates: 2 });
+        assert_eq!(error.to_string(), "WS-COUNT is ambiguous: 2 items match, qualify it with OF or IN");
+        let target = &move_stmt.targets()[0];
+        assert_eq!(data.resolve(target).unwrap().picture().map(|p| p.size), Some(4));
+        let missing = super::parse("PROCEDURE DIVISION.\n    MOVE WS-NONE TO WS-COUNT.\n");
+        let missing = missing.syntax().descendants().find_map(DataRef::cast).unwrap();
+        assert_eq!(data.resolve(&missing), Err(ResolveError::Undefined("WS-NONE".to_string())));
+    }
+
+    #[test]
+    fn test_function_calls() {
+        let source = r#"
+PROCEDURE DIVISION.
+    MOVE FUNCTION CURRENT-DATE (1:8) TO WS-TODAY.
+    COMPUTE WS-AMT = FUNCTION NUMVAL(WS-INPUT) * 2.
+    COMPUTE WS-HIGH = FUNCTION MAX(WS-A WS-B, WS-C + 1).
+    MOVE FUNCTION UPPER-CASE(WS-NAME)(1:3) TO WS-CODE.
+    COMPUTE WS-TOTAL = FUNCTION SUM(WS-QTY(ALL)) + FUNCTION RANDOM.
+    DISPLAY FUNCTION TRIM(WS-NAME TRAILING).
+    IF FUNCTION MOD(WS-YEAR, 4) = 0 DISPLAY "LEAP".
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert!(parse.warnings.is_empty(), "Warnings: {:?}", parse.warnings);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let calls: Vec<_> = parse.syntax().descendants().filter_map(FunctionCall::cast).collect();
+        let names: Vec<_> = calls.iter().filter_map(|c| c.name()).collect();
+        assert_eq!(
+            names,
+            vec!["CURRENT-DATE", "NUMVAL", "MAX", "UPPER-CASE", "SUM", "RANDOM", "TRIM", "MOD"]
+        );
+
+        assert!(calls[0].arguments().is_empty());
+        assert_eq!(calls[0].ref_mod().and_then(|r| r.length()).unwrap().syntax().text().to_string(), "8");
+        assert_eq!(calls[0].intrinsic().unwrap().category, intrinsics::FunctionCategory::Alphanumeric);
+        assert_eq!(calls[2].arguments().len(), 3);
+        assert!(matches!(calls[2].arguments()[2].kind(), ExprKind::Binary(_)));
+        assert_eq!(calls[3].arguments().len(), 1);
+        assert!(calls[3].ref_mod().is_some());
+        let ExprKind::DataRef(table) = calls[4].arguments()[0].kind() else { panic!("expected table") };
+        assert_eq!(table.subscripts(), vec![Subscript::All]);
+        assert_eq!(calls[6].arguments().len(), 1);
+
+        // The call is an operand of MOVE and of the relation
+        let moves: Vec<_> = parse.syntax().descendants().filter_map(MoveStmt::cast).collect();
+        assert!(matches!(moves[0].source().unwrap().kind(), OperandKind::FunctionCall(_)));
+        let condition = parse.syntax().descendants().find_map(IfStmt::cast).unwrap().condition().unwrap();
+        assert!(matches!(condition.kind(), ConditionKind::Relation(_)));
+
+        let misuse = super::parse("PROCEDURE DIVISION.\n    COMPUTE X = FUNCTION MOD(A) + FUNCTION SHOUT(B).\n");
+        assert_eq!(misuse.errors, vec!["FUNCTION MOD takes 2 arguments, found 1"]);
+        assert_eq!(misuse.warnings, vec!["Unknown intrinsic function SHOUT"]);
+    }
+
+    #[test]
+    fn test_unparsed_text_is_kept() {
+        let source = "PROGRAM-ID. P.\nGARBAGE HERE.\n";
+        let parse = parse(source);
+        assert_eq!(parse.errors, vec!["Unexpected Some(IDENT)".to_string()]);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        // Stray words inside a sentence are reported, then skipped
+        let source = "PROCEDURE DIVISION.\n    DISPLAY \"A\" THEN DISPLAY \"B\".\n";
+        let stray = super::parse(source);
+        assert_eq!(stray.errors, vec!["Unexpected Some(THEN_KW) in sentence".to_string()]);
+        assert_eq!(stray.syntax().text().to_string(), source);
+        assert_eq!(stray.root().unwrap().procedure_division().unwrap().display_statements().count(), 2);
+    }
+
+    #[test]
+    fn test_numeric_procedure_names() {
+        let source = "PROCEDURE DIVISION.\n100-INIT.\n    DISPLAY \"A\".\n200.\n    DISPLAY \"B\".\n\
+                      0300 SECTION.\n400.\n    DISPLAY \"C\".\n";
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let procedure = parse.root().unwrap().procedure_division().unwrap();
+        let paragraphs: Vec<_> = procedure.syntax().descendants().filter_map(Paragraph::cast).collect();
+        let names: Vec<_> = paragraphs.iter().filter_map(|p| p.name()).collect();
+        assert_eq!(names, vec!["100-INIT", "200", "400"]);
+        assert_eq!(paragraphs[2].section().and_then(|s| s.name()), Some("0300".to_string()));
+        assert_eq!(procedure.display_statements().count(), 3);
+    }
+
+    #[test]
+    fn test_fixed_format_comment_lines() {
+        let source = "\
+000100 IDENTIFICATION DIVISION.
+000200 PROGRAM-ID. HELLO.
+000300 PROCEDURE DIVISION.
+000400* Say hello.
+000500/ New page, still a comment: \"unterminated
+000600     DISPLAY \"Hello\".
+";
+
+        let parse = parse_with_options(source, SourceOptions { format: SourceFormat::Fixed, ..SourceOptions::default() });
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        assert_eq!(parse.syntax().text().to_string(), source);
+
+        let root = parse.root().unwrap();
+        let displays: Vec<_> = root.procedure_division().unwrap().display_statements().collect();
+        assert_eq!(displays.len(), 1);
+        assert_eq!(
+            displays[0].leading_comments(),
+            vec![
+                "Say hello.".to_string(),
+                "New page, still a comment: \"unterminated".to_string()
+            ]
+        );
     }
 }
 
@@ -455,6 +8453,15 @@ PROCEDURE DIVISION.
         }
     }
 
+    println!("\n=== Parse Warnings ===");
+    if parse.warnings.is_empty() {
+        println!("No warnings");
+    } else {
+        for warning in &parse.warnings {
+            println!("  - {}", warning);
+        }
+    }
+
     println!("\n=== Syntax Tree ===");
     println!("{:#?}", parse.syntax());
 
@@ -0,0 +1,197 @@
+//! Catalogue of the standard intrinsic functions
+//!
+//! Each function is listed with the number of arguments it takes and the
+//! category of its result, so that `FUNCTION` references can be checked
+//! against it. Functions whose result has the category of their arguments,
+//! such as `MAX` or `UPPER-CASE`, are marked as such, as are numeric
+//! functions like `ABS` that return an integer for integer arguments.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FunctionCategory {
+    Alphanumeric,
+    National,
+    Numeric,
+    Integer,
+    Boolean,
+    /// Numeric arguments only, and an integer when every argument is an
+    /// integer, as for `ABS` or `RANGE`.
+    IntegerIfArgumentsInteger,
+    /// The category of the arguments, e.g. alphanumeric for
+    /// `UPPER-CASE` of an alphanumeric item.
+    SameAsArgument,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntrinsicFunction {
+    pub name: &'static str,
+    pub min_args: usize,
+    /// `None` for functions taking any number of arguments, such as `MAX`.
+    pub max_args: Option<usize>,
+    pub category: FunctionCategory,
+}
+
+impl IntrinsicFunction {
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.min_args && self.max_args.is_none_or(|max| count <= max)
+    }
+
+    /// The accepted argument counts in words, as in "1 to 3 arguments".
+    pub fn arity(&self) -> String {
+        let noun = |n: usize| if n == 1 { "argument" } else { "arguments" };
+        match self.max_args {
+            Some(0) => "no arguments".to_string(),
+            Some(max) if max == self.min_args => format!("{} {}", max, noun(max)),
+            Some(max) => format!("{} to {} {}", self.min_args, max, noun(max)),
+            None => format!("at least {} {}", self.min_args, noun(self.min_args)),
+        }
+    }
+}
+
+const fn function(
+    name: &'static str,
+    min_args: usize,
+    max_args: Option<usize>,
+    category: FunctionCategory,
+) -> IntrinsicFunction {
+    IntrinsicFunction { name, min_args, max_args, category }
+}
+
+use FunctionCategory::*;
+
+pub const INTRINSIC_FUNCTIONS: &[IntrinsicFunction] = &[
+    function("ABS", 1, Some(1), IntegerIfArgumentsInteger),
+    function("ACOS", 1, Some(1), Numeric),
+    function("ANNUITY", 2, Some(2), Numeric),
+    function("ASIN", 1, Some(1), Numeric),
+    function("ATAN", 1, Some(1), Numeric),
+    function("BOOLEAN-OF-INTEGER", 2, Some(2), Boolean),
+    function("BYTE-LENGTH", 1, Some(1), Integer),
+    function("CHAR", 1, Some(1), Alphanumeric),
+    function("CHAR-NATIONAL", 1, Some(1), National),
+    function("COMBINED-DATETIME", 2, Some(2), Numeric),
+    function("CONCATENATE", 1, None, SameAsArgument),
+    function("COS", 1, Some(1), Numeric),
+    function("CURRENCY-SYMBOL", 0, Some(0), Alphanumeric),
+    function("CURRENT-DATE", 0, Some(0), Alphanumeric),
+    function("DATE-OF-INTEGER", 1, Some(1), Integer),
+    function("DATE-TO-YYYYMMDD", 1, Some(3), Integer),
+    function("DAY-OF-INTEGER", 1, Some(1), Integer),
+    function("DAY-TO-YYYYDDD", 1, Some(3), Integer),
+    function("DISPLAY-OF", 1, Some(2), Alphanumeric),
+    function("E", 0, Some(0), Numeric),
+    function("EXCEPTION-FILE", 0, Some(0), Alphanumeric),
+    function("EXCEPTION-FILE-N", 0, Some(0), National),
+    function("EXCEPTION-LOCATION", 0, Some(0), Alphanumeric),
+    function("EXCEPTION-LOCATION-N", 0, Some(0), National),
+    function("EXCEPTION-STATEMENT", 0, Some(0), Alphanumeric),
+    function("EXCEPTION-STATUS", 0, Some(0), Alphanumeric),
+    function("EXP", 1, Some(1), Numeric),
+    function("EXP10", 1, Some(1), Numeric),
+    function("FACTORIAL", 1, Some(1), Integer),
+    function("FORMATTED-CURRENT-DATE", 1, Some(1), Alphanumeric),
+    function("FORMATTED-DATE", 2, Some(2), Alphanumeric),
+    function("FORMATTED-DATETIME", 3, Some(4), Alphanumeric),
+    function("FORMATTED-TIME", 2, Some(3), Alphanumeric),
+    function("FRACTION-PART", 1, Some(1), Numeric),
+    function("HIGHEST-ALGEBRAIC", 1, Some(1), IntegerIfArgumentsInteger),
+    function("INTEGER", 1, Some(1), Integer),
+    function("INTEGER-OF-BOOLEAN", 1, Some(1), Integer),
+    function("INTEGER-OF-DATE", 1, Some(1), Integer),
+    function("INTEGER-OF-DAY", 1, Some(1), Integer),
+    function("INTEGER-OF-FORMATTED-DATE", 2, Some(2), Integer),
+    function("INTEGER-PART", 1, Some(1), Integer),
+    function("LENGTH", 1, Some(1), Integer),
+    function("LENGTH-AN", 1, Some(1), Integer),
+    function("LOCALE-COMPARE", 2, Some(3), Alphanumeric),
+    function("LOCALE-DATE", 1, Some(2), Alphanumeric),
+    function("LOCALE-TIME", 1, Some(2), Alphanumeric),
+    function("LOCALE-TIME-FROM-SECONDS", 1, Some(2), Alphanumeric),
+    function("LOG", 1, Some(1), Numeric),
+    function("LOG10", 1, Some(1), Numeric),
+    function("LOWER-CASE", 1, Some(1), SameAsArgument),
+    function("LOWEST-ALGEBRAIC", 1, Some(1), IntegerIfArgumentsInteger),
+    function("MAX", 1, None, SameAsArgument),
+    function("MEAN", 1, None, Numeric),
+    function("MEDIAN", 1, None, Numeric),
+    function("MIDRANGE", 1, None, Numeric),
+    function("MIN", 1, None, SameAsArgument),
+    function("MOD", 2, Some(2), Integer),
+    // The argument is one of the keywords ACTIVATING, CURRENT, NESTED,
+    // REPOSITORY or TOP-LEVEL
+    function("MODULE-NAME", 1, Some(1), Alphanumeric),
+    function("NATIONAL-OF", 1, Some(2), National),
+    function("NUMVAL", 1, Some(1), Numeric),
+    function("NUMVAL-C", 1, Some(2), Numeric),
+    function("NUMVAL-F", 1, Some(1), Numeric),
+    function("ORD", 1, Some(1), Integer),
+    function("ORD-MAX", 1, None, Integer),
+    function("ORD-MIN", 1, None, Integer),
+    function("PI", 0, Some(0), Numeric),
+    function("PRESENT-VALUE", 2, None, Numeric),
+    function("RANDOM", 0, Some(1), Numeric),
+    function("RANGE", 1, None, IntegerIfArgumentsInteger),
+    function("REM", 2, Some(2), Numeric),
+    function("REVERSE", 1, Some(1), SameAsArgument),
+    function("SECONDS-FROM-FORMATTED-TIME", 2, Some(2), Numeric),
+    function("SECONDS-PAST-MIDNIGHT", 0, Some(0), Numeric),
+    function("SIGN", 1, Some(1), Integer),
+    function("SIN", 1, Some(1), Numeric),
+    function("SQRT", 1, Some(1), Numeric),
+    function("STANDARD-COMPARE", 2, Some(4), Alphanumeric),
+    function("STANDARD-DEVIATION", 1, None, Numeric),
+    // The string followed by pairs of text to find and its replacement
+    function("SUBSTITUTE", 3, None, SameAsArgument),
+    function("SUBSTITUTE-CASE", 3, None, SameAsArgument),
+    function("SUM", 1, None, IntegerIfArgumentsInteger),
+    function("TAN", 1, Some(1), Numeric),
+    function("TEST-DATE-YYYYMMDD", 1, Some(1), Integer),
+    function("TEST-DAY-YYYYDDD", 1, Some(1), Integer),
+    function("TEST-FORMATTED-DATETIME", 2, Some(2), Integer),
+    function("TEST-NUMVAL", 1, Some(1), Integer),
+    function("TEST-NUMVAL-C", 1, Some(2), Integer),
+    function("TEST-NUMVAL-F", 1, Some(1), Integer),
+    // The optional second argument is the keyword LEADING or TRAILING
+    function("TRIM", 1, Some(2), SameAsArgument),
+    function("UPPER-CASE", 1, Some(1), SameAsArgument),
+    function("VARIANCE", 1, None, Numeric),
+    function("WHEN-COMPILED", 0, Some(0), Alphanumeric),
+    function("YEAR-TO-YYYY", 1, Some(3), Integer),
+];
+
+/// The function named `name`, in any case.
+pub fn lookup(name: &str) -> Option<&'static IntrinsicFunction> {
+    INTRINSIC_FUNCTIONS.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intrinsic_catalogue() {
+        let current_date = lookup("current-date").unwrap();
+        assert_eq!(current_date.category, FunctionCategory::Alphanumeric);
+        assert!(current_date.accepts(0) && !current_date.accepts(1));
+        assert_eq!(current_date.arity(), "no arguments");
+
+        let max = lookup("MAX").unwrap();
+        assert!(!max.accepts(0) && max.accepts(1) && max.accepts(20));
+        assert_eq!(max.arity(), "at least 1 argument");
+
+        assert_eq!(lookup("MOD").unwrap().arity(), "2 arguments");
+        assert_eq!(lookup("YEAR-TO-YYYY").unwrap().arity(), "1 to 3 arguments");
+        assert!(lookup("NO-SUCH-FUNCTION").is_none());
+
+        for name in ["TEST-DATE-YYYYMMDD", "TEST-DAY-YYYYDDD"] {
+            assert_eq!(lookup(name).unwrap().category, FunctionCategory::Integer);
+        }
+        assert_eq!(lookup("SECONDS-PAST-MIDNIGHT").unwrap().arity(), "no arguments");
+        // ABS and RANGE are numeric only, unlike MAX which takes any category
+        assert_eq!(lookup("ABS").unwrap().category, FunctionCategory::IntegerIfArgumentsInteger);
+        assert_eq!(lookup("RANGE").unwrap().category, FunctionCategory::IntegerIfArgumentsInteger);
+        assert_eq!(lookup("MAX").unwrap().category, FunctionCategory::SameAsArgument);
+
+        // Sorted, so new entries go in their place
+        assert!(INTRINSIC_FUNCTIONS.windows(2).all(|w| w[0].name < w[1].name));
+    }
+}
@@ -0,0 +1,316 @@
+//! Storage layout of data records
+//!
+//! Computes the byte offset and length of every item of a record from its
+//! PICTURE and USAGE, with REDEFINES overlays, OCCURS repetition and the
+//! slack bytes SYNCHRONIZED items need for alignment. Sizes follow IBM
+//! Enterprise COBOL: binary items take 2, 4 or 8 bytes depending on their
+//! digits, pointers and indexes 4 bytes. Offsets and lengths too large for a
+//! `u32` are capped at `u32::MAX`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::picture::{Picture, PictureCategory};
+use super::{DataDivision, DataItem, Usage};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub level: u8,
+    /// `None` for FILLER
+    pub name: Option<String>,
+    /// Nesting depth below the record, which is at depth 0.
+    pub depth: usize,
+    /// Byte offset of the first occurrence from the start of the record.
+    pub offset: u32,
+    /// Length in bytes of one occurrence.
+    pub length: u32,
+    /// Maximum number of occurrences, for items with an OCCURS clause.
+    pub occurs: Option<u32>,
+    /// `None` for group items.
+    pub usage: Option<Usage>,
+    pub redefines: Option<String>,
+}
+
+impl FieldLayout {
+    /// Length of all occurrences together.
+    pub fn total_length(&self) -> u32 {
+        self.length.saturating_mul(self.occurs.unwrap_or(1))
+    }
+}
+
+/// The items of one level 01 or 77 record in source order, the record first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordLayout {
+    pub fields: Vec<FieldLayout>,
+}
+
+impl RecordLayout {
+    pub fn compute(record: &DataItem) -> Self {
+        let mut fields = Vec::new();
+        layout_item(record, 0, 0, Usage::Display, &mut fields);
+        RecordLayout { fields }
+    }
+
+    /// Layouts of the records of every section and file description.
+    pub fn all(data: &DataDivision) -> Vec<Self> {
+        let mut records = Vec::new();
+        if let Some(section) = data.file_section() {
+            for fd in section.file_descriptions() {
+                records.extend(fd.records().collect::<Vec<_>>());
+            }
+        }
+        if let Some(section) = data.working_storage_section() {
+            records.extend(section.items().collect::<Vec<_>>());
+        }
+        if let Some(section) = data.local_storage_section() {
+            records.extend(section.items().collect::<Vec<_>>());
+        }
+        if let Some(section) = data.linkage_section() {
+            records.extend(section.items().collect::<Vec<_>>());
+        }
+        records
+            .iter()
+            .filter(|record| matches!(record.level(), Some(1 | 77)))
+            .map(Self::compute)
+            .collect()
+    }
+
+    pub fn record(&self) -> &FieldLayout {
+        &self.fields[0]
+    }
+
+    pub fn length(&self) -> u32 {
+        self.record().total_length()
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields
+            .iter()
+            .find(|f| f.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+}
+
+/// A listing map: one line per item with its offset, length and usage.
+impl fmt::Display for RecordLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<36} {:>8} {:>8}  USAGE", "LVL NAME", "OFFSET", "LENGTH")?;
+        for field in &self.fields {
+            let name = format!(
+                "{}{:02} {}",
+                "  ".repeat(field.depth),
+                field.level,
+                field.name.as_deref().unwrap_or("FILLER")
+            );
+            let usage = match field.usage {
+                Some(Usage::Display) => "DISPLAY",
+                Some(Usage::Binary) => "BINARY",
+                Some(Usage::Float) => "COMP-1",
+                Some(Usage::Double) => "COMP-2",
+                Some(Usage::PackedDecimal) => "COMP-3",
+                Some(Usage::NativeBinary) => "COMP-5",
+                Some(Usage::Pointer) => "POINTER",
+                Some(Usage::Index) => "INDEX",
+                None => "GROUP",
+            };
+            write!(f, "{:<36} {:>8} {:>8}  {}", name, field.offset, field.length, usage)?;
+            if let Some(occurs) = field.occurs {
+                write!(f, " OCCURS {}", occurs)?;
+            }
+            if let Some(redefined) = &field.redefines {
+                write!(f, " REDEFINES {}", redefined)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lays out `item` starting at `offset` and returns its actual offset, which
+/// differs when slack bytes are needed, and the length of all occurrences.
+fn layout_item(
+    item: &DataItem,
+    offset: u32,
+    depth: usize,
+    inherited: Usage,
+    fields: &mut Vec<FieldLayout>,
+) -> (u32, u32) {
+    let usage = item.usage().unwrap_or(inherited);
+    let index = fields.len();
+    fields.push(FieldLayout {
+        level: item.level().unwrap_or(0),
+        name: item.name(),
+        depth,
+        offset,
+        length: 0,
+        occurs: item.occurs_clause().and_then(|o| o.max()),
+        usage: None,
+        redefines: item.redefines(),
+    });
+
+    let children: Vec<_> = item
+        .children()
+        .filter(|child| !matches!(child.level(), Some(66 | 88)))
+        .collect();
+
+    let (offset, length) = if children.is_empty() {
+        let length = elementary_length(item, usage);
+        let offset = if item.synchronized_clause().is_some() {
+            offset.checked_next_multiple_of(alignment(usage, length)).unwrap_or(u32::MAX)
+        } else {
+            offset
+        };
+        fields[index].usage = Some(usage);
+        (offset, length)
+    } else {
+        let mut position = offset;
+        let mut starts = HashMap::new();
+        for child in &children {
+            let start = child
+                .redefines()
+                .and_then(|name| starts.get(&name.to_uppercase()).copied())
+                .unwrap_or(position);
+            let (start, length) = layout_item(child, start, depth + 1, usage, fields);
+            if let Some(name) = child.name() {
+                starts.insert(name.to_uppercase(), start);
+            }
+            position = position.max(start.saturating_add(length));
+        }
+        (offset, position - offset)
+    };
+
+    let field = &mut fields[index];
+    field.offset = offset;
+    field.length = length;
+    (offset, field.total_length())
+}
+
+fn elementary_length(item: &DataItem, usage: Usage) -> u32 {
+    let picture = item.picture();
+    match usage {
+        Usage::Display => {
+            let Some(picture) = picture else { return 0 };
+            let separate_sign = item.sign_clause().is_some_and(|s| s.is_separate())
+                && picture.category == PictureCategory::Numeric
+                && picture.signed;
+            match picture.category {
+                PictureCategory::National | PictureCategory::NationalEdited => picture.size.saturating_mul(2),
+                _ => picture.size.saturating_add(u32::from(separate_sign)),
+            }
+        }
+        Usage::Binary | Usage::NativeBinary => match picture.as_ref().map_or(0, digit_positions) {
+            0..=4 => 2,
+            5..=9 => 4,
+            _ => 8,
+        },
+        Usage::Float => 4,
+        Usage::Double => 8,
+        Usage::PackedDecimal => picture.as_ref().map_or(0, digit_positions) / 2 + 1,
+        Usage::Pointer | Usage::Index => 4,
+    }
+}
+
+/// Digits actually stored, i.e. without `P` scaling positions.
+fn digit_positions(picture: &Picture) -> u32 {
+    picture
+        .symbols
+        .iter()
+        .filter(|s| s.symbol == '9')
+        .map(|s| s.count)
+        .sum()
+}
+
+/// Boundary a SYNCHRONIZED item is aligned on.
+fn alignment(usage: Usage, length: u32) -> u32 {
+    match usage {
+        Usage::Binary | Usage::NativeBinary | Usage::Float | Usage::Double => length,
+        Usage::Pointer | Usage::Index => 4,
+        Usage::Display | Usage::PackedDecimal => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cobol::parse;
+
+    #[test]
+    fn test_record_layout() {
+        let source = r#"
+DATA DIVISION.
+WORKING-STORAGE SECTION.
+01 WS-RECORD.
+   05 WS-CODE PIC X(3).
+   05 WS-COUNT PIC S9(4) COMP SYNC.
+   05 WS-AMOUNT PIC S9(7)V99 COMP-3.
+   05 WS-DATE PIC 9(8).
+   05 WS-DATE-PARTS REDEFINES WS-DATE.
+      10 WS-YEAR PIC 9(4).
+      10 WS-MONTH PIC 99.
+      10 WS-DAY PIC 99.
+   05 WS-LINES OCCURS 3 TIMES.
+      10 WS-QTY PIC 9(3) USAGE DISPLAY.
+      10 WS-PRICE PIC S9(5) SIGN LEADING SEPARATE.
+   05 WS-RATE COMP-2.
+77 WS-FLAG PIC X.
+"#;
+
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        let data = parse.root().unwrap().data_division().unwrap();
+        let layouts = RecordLayout::all(&data);
+        assert_eq!(layouts.len(), 2);
+
+        let record = &layouts[0];
+        let at = |name: &str| {
+            let field = record.field(name).unwrap();
+            (field.offset, field.length)
+        };
+        assert_eq!(at("WS-CODE"), (0, 3));
+        // One slack byte aligns the halfword
+        assert_eq!(at("WS-COUNT"), (4, 2));
+        assert_eq!(at("WS-AMOUNT"), (6, 5));
+        assert_eq!(at("WS-DATE"), (11, 8));
+        assert_eq!(at("WS-DATE-PARTS"), (11, 8));
+        assert_eq!(at("WS-MONTH"), (15, 2));
+        assert_eq!(at("WS-LINES"), (19, 9));
+        assert_eq!(record.field("WS-LINES").unwrap().total_length(), 27);
+        assert_eq!(at("WS-PRICE"), (22, 6));
+        assert_eq!(at("WS-RATE"), (46, 8));
+        assert_eq!(record.length(), 54);
+        assert_eq!(layouts[1].length(), 1);
+
+        let listing = record.to_string();
+        assert_eq!(
+            listing,
+            "\
+LVL NAME                               OFFSET   LENGTH  USAGE
+01 WS-RECORD                                0       54  GROUP
+  05 WS-CODE                                0        3  DISPLAY
+  05 WS-COUNT                               4        2  BINARY
+  05 WS-AMOUNT                              6        5  COMP-3
+  05 WS-DATE                               11        8  DISPLAY
+  05 WS-DATE-PARTS                         11        8  GROUP REDEFINES WS-DATE
+    10 WS-YEAR                             11        4  DISPLAY
+    10 WS-MONTH                            15        2  DISPLAY
+    10 WS-DAY                              17        2  DISPLAY
+  05 WS-LINES                              19        9  GROUP OCCURS 3
+    10 WS-QTY                              19        3  DISPLAY
+    10 WS-PRICE                            22        6  DISPLAY
+  05 WS-RATE                               46        8  COMP-2
+"
+        );
+    }
+
+    #[test]
+    fn test_oversized_record_layout() {
+        let source = "DATA DIVISION.\nWORKING-STORAGE SECTION.\n01 A.\n   05 B PIC X(100000) OCCURS 100000.\n   \
+                      05 C PIC X.\n";
+        let parse = parse(source);
+        assert!(parse.errors.is_empty(), "Errors: {:?}", parse.errors);
+        let data = parse.root().unwrap().data_division().unwrap();
+        let record = &RecordLayout::all(&data)[0];
+        assert_eq!(record.field("B").unwrap().total_length(), u32::MAX);
+        assert_eq!(record.field("C").unwrap().offset, u32::MAX);
+        assert_eq!(record.length(), u32::MAX);
+    }
+}
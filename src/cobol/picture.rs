@@ -0,0 +1,371 @@
+//! PICTURE character-string analysis
+//!
+//! A picture string is first split into symbols with their repetition
+//! counts (`9(5)V99` is five `9`, one `V` and two `9`), then classified into
+//! the data category it describes.
+
+use std::fmt;
+
+/// Largest number of digit positions a numeric picture may describe.
+const MAX_DIGITS: u32 = 38;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PictureCategory {
+    /// `A` only
+    Alphabetic,
+    /// `X`, or `A` mixed with `9`
+    Alphanumeric,
+    /// Alphanumeric with `B`, `0` or `/` insertion
+    AlphanumericEdited,
+    /// `9`, `S`, `V` and `P` only
+    Numeric,
+    /// Digit positions with editing symbols such as `Z`, `,`, `.`, `-`, `CR`
+    NumericEdited,
+    /// `N` only
+    National,
+    /// `N` with `B`, `0` or `/` insertion
+    NationalEdited,
+}
+
+/// A picture symbol together with how many times it occurs in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictureSymbol {
+    /// The symbol in upper case. `CR` and `DB` are represented by `C` and `D`.
+    pub symbol: char,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    pub symbols: Vec<PictureSymbol>,
+    pub category: PictureCategory,
+    /// Digit positions before the decimal point, scaling positions included.
+    pub integer_digits: u32,
+    /// Digit positions after the decimal point, scaling positions included.
+    pub fraction_digits: u32,
+    pub signed: bool,
+    /// Number of character positions, i.e. the storage length of the item
+    /// with USAGE DISPLAY.
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PictureError {
+    Empty,
+    /// A character that is not a picture symbol, at a 1-based position.
+    UnexpectedCharacter { ch: char, position: usize },
+    UnclosedParenthesis { position: usize },
+    BadRepetition { position: usize },
+    /// A symbol that may appear at most once.
+    Repeated(char),
+    Misplaced(char),
+    MixedCategories,
+    TooManyDigits(u32),
+    /// More character positions than can be counted.
+    TooLong,
+}
+
+impl fmt::Display for PictureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PictureError::Empty => write!(f, "empty PICTURE string"),
+            PictureError::UnexpectedCharacter { ch, position } => {
+                write!(f, "unexpected character '{}' at position {}", ch, position)
+            }
+            PictureError::UnclosedParenthesis { position } => {
+                write!(f, "unclosed repetition count at position {}", position)
+            }
+            PictureError::BadRepetition { position } => {
+                write!(f, "invalid repetition count at position {}", position)
+            }
+            PictureError::Repeated(ch) => write!(f, "'{}' may appear only once", symbol_name(*ch)),
+            PictureError::Misplaced(ch) => {
+                write!(f, "'{}' is not allowed at this position", symbol_name(*ch))
+            }
+            PictureError::MixedCategories => write!(f, "symbols of different categories are mixed"),
+            PictureError::TooManyDigits(n) => {
+                write!(f, "{} digit positions exceed the maximum of {}", n, MAX_DIGITS)
+            }
+            PictureError::TooLong => write!(f, "too many character positions"),
+        }
+    }
+}
+
+impl std::error::Error for PictureError {}
+
+fn symbol_name(symbol: char) -> &'static str {
+    match symbol {
+        'C' => "CR",
+        'D' => "DB",
+        'S' => "S",
+        'V' => "V",
+        '.' => ".",
+        ',' => ",",
+        _ => "symbol",
+    }
+}
+
+const NUMERIC_EDITING: &[char] = &['Z', '*', '+', '-', '$', '.', ',', 'C', 'D'];
+const INSERTION: &[char] = &['B', '0', '/'];
+
+impl Picture {
+    pub fn parse(text: &str) -> Result<Self, PictureError> {
+        Self::parse_with_decimal_point(text, false)
+    }
+
+    /// Like [`Picture::parse`], but with `DECIMAL-POINT IS COMMA` in effect
+    /// when `decimal_point_is_comma` is set: `,` is then the decimal point and
+    /// `.` the insertion character.
+    pub fn parse_with_decimal_point(text: &str, decimal_point_is_comma: bool) -> Result<Self, PictureError> {
+        let point = if decimal_point_is_comma { ',' } else { '.' };
+        let symbols = lex_symbols(text)?;
+        let has = |set: &[char]| symbols.iter().any(|s| set.contains(&s.symbol));
+        let total = |symbol: char| -> u32 {
+            symbols.iter().filter(|s| s.symbol == symbol).fold(0, |sum, s| sum.saturating_add(s.count))
+        };
+
+        for symbol in ['S', 'V', point, 'C', 'D'] {
+            if total(symbol) > 1 {
+                return Err(PictureError::Repeated(symbol));
+            }
+        }
+        if has(&['S']) && symbols[0].symbol != 'S' {
+            return Err(PictureError::Misplaced('S'));
+        }
+        if let Some(pos) = symbols.iter().position(|s| matches!(s.symbol, 'C' | 'D'))
+            && pos != symbols.len() - 1
+        {
+            return Err(PictureError::Misplaced(symbols[pos].symbol));
+        }
+
+        let category = if has(&['N']) {
+            if symbols.iter().any(|s| !matches!(s.symbol, 'N' | 'B' | '0' | '/')) {
+                return Err(PictureError::MixedCategories);
+            }
+            if has(INSERTION) { PictureCategory::NationalEdited } else { PictureCategory::National }
+        } else if has(&['A', 'X']) {
+            if has(NUMERIC_EDITING) || has(&['S', 'V', 'P']) {
+                return Err(PictureError::MixedCategories);
+            }
+            if has(INSERTION) {
+                PictureCategory::AlphanumericEdited
+            } else if symbols.iter().all(|s| s.symbol == 'A') {
+                PictureCategory::Alphabetic
+            } else {
+                PictureCategory::Alphanumeric
+            }
+        } else if has(NUMERIC_EDITING) || has(INSERTION) {
+            if has(&['S']) {
+                return Err(PictureError::Misplaced('S'));
+            }
+            if has(&['V']) && has(&[point]) {
+                return Err(PictureError::Repeated('V'));
+            }
+            PictureCategory::NumericEdited
+        } else {
+            PictureCategory::Numeric
+        };
+
+        let mut picture = Picture {
+            category,
+            integer_digits: 0,
+            fraction_digits: 0,
+            signed: has(&['S', '+', '-', 'C', 'D']),
+            size: symbols
+                .iter()
+                .try_fold(0u32, |size, s| match s.symbol {
+                    'S' | 'V' | 'P' => Some(size),
+                    'C' | 'D' => size.checked_add(s.count.checked_mul(2)?),
+                    _ => size.checked_add(s.count),
+                })
+                .ok_or(PictureError::TooLong)?,
+            symbols: Vec::new(),
+        };
+
+        if matches!(category, PictureCategory::Numeric | PictureCategory::NumericEdited) {
+            // The first symbol of a floating insertion string (`$$$9`, `--9`)
+            // is not a digit position; a single `+`, `-` or `$` is none at all.
+            // `P` before any other digit position scales to the right of the
+            // point, as in `PPP999`, which has six fraction digits.
+            let mut floating_seen = Vec::new();
+            let mut after_point = false;
+            let mut digit_seen = false;
+            for s in &symbols {
+                let digits = match s.symbol {
+                    'P' if !digit_seen => {
+                        after_point = true;
+                        s.count
+                    }
+                    '9' | 'P' | 'Z' | '*' => s.count,
+                    '+' | '-' | '$' if total(s.symbol) > 1 => {
+                        if floating_seen.contains(&s.symbol) {
+                            s.count
+                        } else {
+                            floating_seen.push(s.symbol);
+                            s.count - 1
+                        }
+                    }
+                    ch if ch == 'V' || ch == point => {
+                        after_point = true;
+                        0
+                    }
+                    _ => 0,
+                };
+                digit_seen |= digits > 0;
+                if after_point {
+                    picture.fraction_digits = picture.fraction_digits.saturating_add(digits);
+                } else {
+                    picture.integer_digits = picture.integer_digits.saturating_add(digits);
+                }
+            }
+            let digits = picture.digits();
+            if digits > MAX_DIGITS {
+                return Err(PictureError::TooManyDigits(digits));
+            }
+        }
+
+        picture.symbols = symbols;
+        Ok(picture)
+    }
+
+    /// Total digit positions, scaling positions included.
+    pub fn digits(&self) -> u32 {
+        self.integer_digits.saturating_add(self.fraction_digits)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.category == PictureCategory::Numeric
+    }
+}
+
+/// Splits a picture string into symbols, merging repetitions such as `99`
+/// and `9(2)` into a single entry.
+fn lex_symbols(text: &str) -> Result<Vec<PictureSymbol>, PictureError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut symbols: Vec<PictureSymbol> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i].to_ascii_uppercase();
+        let unexpected = PictureError::UnexpectedCharacter { ch: chars[i], position: i + 1 };
+        let symbol = match ch {
+            'C' | 'D' => {
+                let second = if ch == 'C' { 'R' } else { 'B' };
+                if chars.get(i + 1).map(char::to_ascii_uppercase) != Some(second) {
+                    return Err(unexpected);
+                }
+                i += 1;
+                ch
+            }
+            'A' | 'X' | '9' | 'N' | 'S' | 'V' | 'P' | 'Z' | '*' | '+' | '-' | '$' | '.' | ','
+            | '/' | 'B' | '0' => ch,
+            _ => return Err(unexpected),
+        };
+        i += 1;
+
+        let mut count = 1;
+        if chars.get(i) == Some(&'(') {
+            let close = chars[i..]
+                .iter()
+                .position(|&c| c == ')')
+                .map(|p| i + p)
+                .ok_or(PictureError::UnclosedParenthesis { position: i + 1 })?;
+            count = chars[i + 1..close]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or(PictureError::BadRepetition { position: i + 1 })?;
+            i = close + 1;
+        }
+
+        match symbols.last_mut() {
+            Some(last) if last.symbol == symbol => {
+                last.count = last.count.checked_add(count).ok_or(PictureError::TooLong)?;
+            }
+            _ => symbols.push(PictureSymbol { symbol, count }),
+        }
+    }
+
+    if symbols.is_empty() {
+        return Err(PictureError::Empty);
+    }
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picture_categories() {
+        let p = Picture::parse("9(5)V99").unwrap();
+        assert_eq!(p.category, PictureCategory::Numeric);
+        assert_eq!((p.integer_digits, p.fraction_digits, p.signed, p.size), (5, 2, false, 7));
+
+        let p = Picture::parse("S9(7)").unwrap();
+        assert_eq!(p.category, PictureCategory::Numeric);
+        assert_eq!((p.integer_digits, p.fraction_digits, p.signed, p.size), (7, 0, true, 7));
+
+        let p = Picture::parse("X(30)").unwrap();
+        assert_eq!(p.category, PictureCategory::Alphanumeric);
+        assert_eq!(p.size, 30);
+
+        let p = Picture::parse("A(10)").unwrap();
+        assert_eq!(p.category, PictureCategory::Alphabetic);
+        assert_eq!(p.size, 10);
+
+        let p = Picture::parse("Z,ZZ9.99-").unwrap();
+        assert_eq!(p.category, PictureCategory::NumericEdited);
+        assert_eq!((p.integer_digits, p.fraction_digits, p.signed, p.size), (4, 2, true, 9));
+
+        let p = Picture::parse("$$,$$9.99CR").unwrap();
+        assert_eq!(p.category, PictureCategory::NumericEdited);
+        assert_eq!((p.integer_digits, p.fraction_digits, p.size), (4, 2, 11));
+
+        let p = Picture::parse("XXBXX").unwrap();
+        assert_eq!(p.category, PictureCategory::AlphanumericEdited);
+
+        let p = Picture::parse("999PPP").unwrap();
+        assert_eq!((p.integer_digits, p.size), (6, 3));
+
+        let p = Picture::parse("SVPPP999").unwrap();
+        assert_eq!((p.integer_digits, p.fraction_digits, p.size), (0, 6, 3));
+        let p = Picture::parse("PPP999").unwrap();
+        assert_eq!((p.integer_digits, p.fraction_digits), (0, 6));
+
+        // With DECIMAL-POINT IS COMMA the roles of `.` and `,` are swapped
+        let p = Picture::parse_with_decimal_point("Z.ZZ9,99", true).unwrap();
+        assert_eq!(p.category, PictureCategory::NumericEdited);
+        assert_eq!((p.integer_digits, p.fraction_digits, p.size), (4, 2, 8));
+        assert_eq!(Picture::parse_with_decimal_point("9,9,9", true), Err(PictureError::Repeated(',')));
+        assert!(Picture::parse_with_decimal_point("9.9.9", true).is_ok());
+    }
+
+    #[test]
+    fn test_picture_errors() {
+        assert_eq!(Picture::parse(""), Err(PictureError::Empty));
+        assert_eq!(
+            Picture::parse("9(5"),
+            Err(PictureError::UnclosedParenthesis { position: 2 })
+        );
+        assert_eq!(Picture::parse("9(0)"), Err(PictureError::BadRepetition { position: 2 }));
+        assert_eq!(
+            Picture::parse("9Q9"),
+            Err(PictureError::UnexpectedCharacter { ch: 'Q', position: 2 })
+        );
+        assert_eq!(Picture::parse("9V9V9"), Err(PictureError::Repeated('V')));
+        assert_eq!(Picture::parse("9S9"), Err(PictureError::Misplaced('S')));
+        assert_eq!(Picture::parse("CR99"), Err(PictureError::Misplaced('C')));
+        assert_eq!(Picture::parse("X(5)V9"), Err(PictureError::MixedCategories));
+        assert_eq!(Picture::parse("9(40)"), Err(PictureError::TooManyDigits(40)));
+        assert_eq!(Picture::parse("X(4294967295)X"), Err(PictureError::TooLong));
+        assert_eq!(Picture::parse("9(4294967295)9(4294967295)"), Err(PictureError::TooLong));
+        assert_eq!(Picture::parse("X(4294967295)BX"), Err(PictureError::TooLong));
+        assert_eq!(Picture::parse("9(4294967295)Z(4294967295)"), Err(PictureError::TooLong));
+        assert_eq!(
+            Picture::parse("9(5)V9V").unwrap_err().to_string(),
+            "'V' may appear only once"
+        );
+    }
+}